@@ -0,0 +1,367 @@
+//! Generates the emoji search index from the Unicode emoji database and the subgroups
+//! in `data/subgroups.toml`, and compiles the slang table in `data/slang.toml` into a
+//! perfect hash map at build time
+
+#[path = "src/normalize.rs"]
+mod normalize;
+
+use normalize::normalize_relaxed;
+use std::collections::HashMap;
+use std::env;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=src/normalize.rs");
+    println!("cargo:rerun-if-changed={}", SLANG_PATH);
+    println!("cargo:rerun-if-changed={}", SUBGROUPS_PATH);
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    let out_dir = Path::new(&out_dir);
+    let subgroups = load_subgroups(Path::new(SUBGROUPS_PATH));
+    write_emoji_index(&out_dir.join("emoji_index.rs"), &subgroups);
+
+    let slang = load_slang(Path::new(SLANG_PATH));
+    write_slang_map(&out_dir.join("slang_map.rs"), &slang);
+    write_slang_index(&out_dir.join("slang_index.rs"), &slang);
+}
+
+/// Slang data file, relative to the package root
+const SLANG_PATH: &str = "data/slang.toml";
+
+/// Subgroup data file, relative to the package root
+const SUBGROUPS_PATH: &str = "data/subgroups.toml";
+
+/// A validated subgroup from the data file
+struct Subgroup {
+    name: String,
+    group: String,
+    /// Position of its first emoji in CLDR order
+    start: usize,
+}
+
+/// Kebab-case name of an emoji group, e.g. "food-and-drink"
+fn group_slug(group: emojis::Group) -> String {
+    let mut slug = String::new();
+    for c in format!("{:?}", group).chars() {
+        if c.is_ascii_uppercase() && !slug.is_empty() {
+            slug.push('-');
+        }
+        slug.push(c.to_ascii_lowercase());
+    }
+    slug
+}
+
+/// Parse and validate the subgroup data file, returning subgroups in CLDR order
+fn load_subgroups(path: &Path) -> Vec<Subgroup> {
+    let source = fs::read_to_string(path)
+        .unwrap_or_else(|err| panic!("failed to read {}: {}", path.display(), err));
+    let table: toml::Table = source
+        .parse()
+        .unwrap_or_else(|err| panic!("{} is not valid TOML: {}", path.display(), err));
+
+    let positions: HashMap<&str, usize> = emojis::iter()
+        .enumerate()
+        .map(|(id, emoji)| (emoji.as_str(), id))
+        .collect();
+    let mut subgroups: Vec<Subgroup> = Vec::new();
+    let mut errors = Vec::new();
+
+    for (group, entries) in &table {
+        let Some(entries) = entries.as_table() else {
+            errors.push(format!("`{}` must be a [group] table", group));
+            continue;
+        };
+
+        for (name, first) in entries {
+            let location = format!("{}.{}", group, name);
+            let Some(first) = first.as_str() else {
+                errors.push(format!(
+                    "{}: expected the first emoji as a string",
+                    location
+                ));
+                continue;
+            };
+            let Some(&start) = positions.get(first) else {
+                errors.push(format!("{}: {:?} is not an emoji", location, first));
+                continue;
+            };
+
+            let actual = group_slug(emojis::get(first).unwrap().group());
+            if actual != *group {
+                errors.push(format!("{}: {:?} is in [{}]", location, first, actual));
+            }
+            if let Some(other) = subgroups
+                .iter()
+                .find(|s| s.name == *name || s.start == start)
+            {
+                errors.push(format!("{}: clashes with {}", location, other.name));
+            }
+
+            subgroups.push(Subgroup {
+                name: name.clone(),
+                group: group.clone(),
+                start,
+            });
+        }
+    }
+
+    subgroups.sort_by_key(|subgroup| subgroup.start);
+    if subgroups
+        .first()
+        .is_some_and(|subgroup| subgroup.start != 0)
+    {
+        errors.push("the first emoji must start a subgroup".to_string());
+    }
+
+    if !errors.is_empty() {
+        panic!(
+            "invalid subgroup data in {}:\n  {}",
+            path.display(),
+            errors.join("\n  ")
+        );
+    }
+
+    subgroups
+}
+
+/// A validated slang term from the data file
+struct SlangTerm {
+    term: String,
+    category: String,
+    emojis: Vec<String>,
+}
+
+/// Parse and validate the slang data file, failing the build on any malformed entry
+fn load_slang(path: &Path) -> Vec<SlangTerm> {
+    let source = fs::read_to_string(path)
+        .unwrap_or_else(|err| panic!("failed to read {}: {}", path.display(), err));
+    let table: toml::Table = source
+        .parse()
+        .unwrap_or_else(|err| panic!("{} is not valid TOML: {}", path.display(), err));
+
+    let mut terms: Vec<SlangTerm> = Vec::new();
+    let mut errors = Vec::new();
+    let mut seen: HashMap<String, String> = HashMap::new();
+
+    for (category, entries) in &table {
+        let Some(entries) = entries.as_table() else {
+            errors.push(format!("`{}` must be a [category] table", category));
+            continue;
+        };
+
+        for (term, value) in entries {
+            let location = format!("{}.{:?}", category, term);
+
+            if term.trim().is_empty() || term.trim() != term || term.to_lowercase() != *term {
+                errors.push(format!("{}: terms must be lowercase and trimmed", location));
+            }
+            if let Some(previous) = seen.insert(term.clone(), category.clone()) {
+                errors.push(format!(
+                    "{}: duplicate term, already in [{}]",
+                    location, previous
+                ));
+            }
+
+            let emojis: Vec<String> = match value.as_array() {
+                Some(values) if !values.is_empty() => values
+                    .iter()
+                    .filter_map(|value| {
+                        let emoji = value.as_str().map(str::to_string);
+                        if emoji.is_none() {
+                            errors.push(format!("{}: {} is not a string", location, value));
+                        }
+                        emoji
+                    })
+                    .collect(),
+                _ => {
+                    errors.push(format!(
+                        "{}: expected a non-empty array of emojis",
+                        location
+                    ));
+                    continue;
+                }
+            };
+
+            for emoji in &emojis {
+                if !is_emoji_text(emoji) {
+                    errors.push(format!("{}: {:?} is not an emoji", location, emoji));
+                }
+            }
+
+            terms.push(SlangTerm {
+                term: term.clone(),
+                category: category.clone(),
+                emojis,
+            });
+        }
+    }
+
+    if !errors.is_empty() {
+        panic!(
+            "invalid slang data in {}:\n  {}",
+            path.display(),
+            errors.join("\n  ")
+        );
+    }
+
+    terms.sort_by(|a, b| a.term.cmp(&b.term));
+    terms
+}
+
+/// Whether the string is emoji or symbols rather than text (e.g. "🔥", "💅👑", "6️⃣7️⃣", "λ")
+fn is_emoji_text(s: &str) -> bool {
+    let is_symbols = |s: &str| !s.chars().any(|c| c.is_ascii() || c.is_whitespace());
+    !s.is_empty() && (is_symbols(s) || is_emoji_sequence(s))
+}
+
+/// Whether the string is one or more emojis from the database, matched longest first
+fn is_emoji_sequence(s: &str) -> bool {
+    let mut rest = s;
+    while !rest.is_empty() {
+        let end = rest
+            .char_indices()
+            .map(|(i, c)| i + c.len_utf8())
+            .rev()
+            .find(|&end| emojis::get(&rest[..end]).is_some());
+        match end {
+            Some(end) => rest = &rest[end..],
+            None => return false,
+        }
+    }
+    true
+}
+
+/// Write the public `SLANG_MAP` perfect hash map
+fn write_slang_map(path: &Path, terms: &[SlangTerm]) {
+    let mut map = phf_codegen::Map::new();
+    for term in terms {
+        map.entry(
+            term.term.as_str(),
+            &format!(
+                "Slang {{ emojis: &{:?}, category: {:?} }}",
+                term.emojis, term.category
+            ),
+        );
+    }
+
+    let code = format!(
+        "/// Custom slang and cultural reference mappings\n\
+         /// Maps common slang terms and cultural references to their corresponding emojis\n\
+         pub static SLANG_MAP: phf::Map<&'static str, Slang> = {};\n",
+        map.build()
+    );
+    fs::write(path, code).expect("failed to write slang map");
+}
+
+/// Write the slang terms sorted alphabetically with their relaxed forms
+fn write_slang_index(path: &Path, terms: &[SlangTerm]) {
+    let mut code = String::from("static SLANG_ENTRIES: &[SlangEntry] = &[\n");
+    for term in terms {
+        writeln!(
+            code,
+            "    SlangEntry {{ term: {:?}, relaxed: {:?}, values: &{:?} }},",
+            term.term,
+            normalize_relaxed(&term.term),
+            term.emojis
+        )
+        .unwrap();
+    }
+    code.push_str("];\n");
+    fs::write(path, code).expect("failed to write slang index");
+}
+
+/// Write the emoji entries, the sorted token table used for exact and prefix lookups,
+/// and the group and subgroup names
+fn write_emoji_index(path: &Path, subgroups: &[Subgroup]) {
+    let mut entries = String::new();
+    let mut ids = phf_codegen::Map::new();
+    let mut tokens: Vec<(String, usize, &str)> = Vec::new();
+
+    for (id, emoji) in emojis::iter().enumerate() {
+        let name = emoji.name().to_lowercase();
+        let subgroup = subgroups
+            .iter()
+            .rev()
+            .find(|subgroup| subgroup.start <= id)
+            .expect("the first emoji starts a subgroup");
+        ids.entry(emoji.as_str(), &id.to_string());
+
+        tokens.push((name.clone(), id, "Name"));
+        for word in name.split_whitespace() {
+            tokens.push((word.to_string(), id, "Word"));
+        }
+
+        let mut shortcodes = String::new();
+        for shortcode in emoji.shortcodes() {
+            let raw = shortcode.trim_matches(':');
+            let lower = raw.to_lowercase();
+            write!(
+                shortcodes,
+                "IndexedShortcode {{ raw: {:?}, lower: {:?}, relaxed: {:?} }}, ",
+                raw,
+                lower,
+                normalize_relaxed(raw)
+            )
+            .unwrap();
+            tokens.push((lower, id, "Shortcode"));
+        }
+
+        writeln!(
+            entries,
+            "    EmojiEntry {{ text: {:?}, name: {:?}, name_relaxed: {:?}, subgroup: {:?}, \
+             shortcodes: &[{}] }},",
+            emoji.as_str(),
+            name,
+            normalize_relaxed(&name),
+            subgroup.name,
+            shortcodes
+        )
+        .unwrap();
+    }
+
+    tokens.sort();
+    tokens.dedup();
+
+    let mut code = String::new();
+    writeln!(
+        code,
+        "static EMOJI_ENTRIES: &[EmojiEntry] = &[\n{}];",
+        entries
+    )
+    .unwrap();
+    writeln!(code, "static TOKENS: &[(&str, usize, TokenKind)] = &[").unwrap();
+    for (token, id, kind) in &tokens {
+        writeln!(code, "    ({:?}, {}, TokenKind::{}),", token, id, kind).unwrap();
+    }
+    writeln!(code, "];").unwrap();
+
+    writeln!(
+        code,
+        "static EMOJI_IDS: phf::Map<&'static str, usize> = {};",
+        ids.build()
+    )
+    .unwrap();
+
+    writeln!(code, "static GROUPS: &[(&str, emojis::Group)] = &[").unwrap();
+    for group in emojis::Group::iter() {
+        writeln!(
+            code,
+            "    ({:?}, emojis::Group::{:?}),",
+            group_slug(group),
+            group
+        )
+        .unwrap();
+    }
+    writeln!(code, "];").unwrap();
+
+    writeln!(code, "static SUBGROUPS: &[(&str, &str)] = &[").unwrap();
+    for subgroup in subgroups {
+        writeln!(code, "    ({:?}, {:?}),", subgroup.name, subgroup.group).unwrap();
+    }
+    writeln!(code, "];").unwrap();
+
+    fs::write(path, code).expect("failed to write emoji index");
+}
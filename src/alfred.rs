@@ -0,0 +1,558 @@
+use crate::describe::{describe, Description};
+use crate::escape::{escape, EscapeFormat};
+use crate::facet::group_name;
+use crate::inspect::inspect;
+use crate::model::{MatchSource, SearchResult};
+use crate::output::match_name;
+use crate::query::QueryError;
+use crate::slang::SLANG_MAP;
+use emojis::{Emoji, SkinTone};
+use serde::Serialize;
+
+/// A single row in Alfred's Script Filter output
+#[derive(Serialize, Debug)]
+pub struct AlfredItem {
+    /// Identifies the emoji with its skin tone and gender, so Alfred learns each pick apart
+    pub uid: String,
+    pub title: String,
+    pub subtitle: String,
+    pub arg: String,
+    pub text: AlfredItemText,
+    /// Page shown with Quick Look (⇧ or ⌘Y), the emoji's Emojipedia entry
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quicklookurl: Option<String>,
+    pub valid: bool,
+    /// Tab completion listing the emoji's skin tones and genders, if it has any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub autocomplete: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mods: Option<AlfredMods>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variables: Option<AlfredVariables>,
+}
+
+/// Workflow variables set when an item is actioned
+#[derive(Serialize, Debug)]
+pub struct AlfredVariables {
+    /// The emoji itself, whatever is copied, for `almoji --record`
+    pub emoji: String,
+}
+
+/// Alternate actions shown while holding a modifier key
+#[derive(Serialize, Debug)]
+pub struct AlfredMods {
+    /// ⌘: copy the shortcode
+    pub cmd: AlfredMod,
+    /// ⌥: copy the codepoints
+    pub alt: AlfredMod,
+    /// ⌃: copy the emoji without a trailing space
+    pub ctrl: AlfredMod,
+    /// ⇧: describe the emoji in the subtitle
+    pub shift: AlfredMod,
+}
+
+/// The argument and subtitle Alfred uses while a modifier is held
+#[derive(Serialize, Debug)]
+pub struct AlfredMod {
+    pub arg: String,
+    pub subtitle: String,
+    pub valid: bool,
+}
+
+impl AlfredMod {
+    fn new(arg: String, subtitle: String) -> Self {
+        AlfredMod {
+            valid: !arg.is_empty(),
+            arg,
+            subtitle,
+        }
+    }
+}
+
+/// Text used by Alfred for copy (⌘C) and large type (⌘L)
+#[derive(Serialize, Debug)]
+pub struct AlfredItemText {
+    pub copy: String,
+    /// The emoji followed by its full description
+    pub largetype: String,
+}
+
+/// Top-level Alfred Script Filter JSON document
+#[derive(Serialize, Debug)]
+pub struct AlfredResponse {
+    pub items: Vec<AlfredItem>,
+    /// Keep Alfred from reordering results by what was picked before
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub skipknowledge: bool,
+}
+
+impl AlfredItem {
+    /// Build an Alfred item for a search result
+    ///
+    /// With an escape format, Enter copies the escape instead of the emoji.
+    pub fn from_result(result: SearchResult, format: Option<EscapeFormat>) -> Self {
+        let autocomplete = result
+            .matched
+            .unicode()
+            .filter(|emoji| variants(emoji).len() > 1)
+            .map(|emoji| emoji.as_str().to_string());
+
+        let name = emojis::get(&result.emoji)
+            .map(|emoji| emoji.name().to_string())
+            .unwrap_or_else(|| match_name(&result.matched));
+        let mut subtitle = subtitle(&name, result.matched.unicode());
+        if result.source == MatchSource::Slang {
+            subtitle = format!("{} · {}", slang_meaning(&result.term), subtitle);
+        }
+
+        AlfredItem {
+            autocomplete,
+            ..AlfredItem::new(&result.emoji, subtitle, result.matched.unicode(), format)
+        }
+    }
+
+    /// Build an item copying `emoji`, using `unicode` for its shortcode
+    fn new(
+        emoji: &str,
+        subtitle: String,
+        unicode: Option<&'static Emoji>,
+        format: Option<EscapeFormat>,
+    ) -> Self {
+        let copied = match format {
+            Some(format) => escape(emoji, format),
+            None => emoji.to_string(),
+        };
+
+        AlfredItem {
+            uid: uid(emoji),
+            title: copied.clone(),
+            subtitle,
+            arg: format!("{} ", copied),
+            text: AlfredItemText {
+                copy: format!("{} ", copied),
+                largetype: largetype(emoji, &copied),
+            },
+            quicklookurl: unicode.map(|_| emojipedia_url(emoji)),
+            valid: true,
+            autocomplete: None,
+            mods: Some(AlfredMods::new(emoji, unicode, &copied)),
+            variables: Some(AlfredVariables {
+                emoji: emoji.to_string(),
+            }),
+        }
+    }
+}
+
+impl AlfredMods {
+    /// Build the modifier actions for an emoji copied as `copied` on Enter
+    fn new(emoji: &str, unicode: Option<&'static Emoji>, copied: &str) -> Self {
+        let shortcode = unicode
+            .and_then(|emoji| emoji.shortcode())
+            .map(|shortcode| format!(":{}:", shortcode))
+            .unwrap_or_default();
+        let shortcode_subtitle = if shortcode.is_empty() {
+            "No shortcode for this emoji".to_string()
+        } else {
+            format!("Copy {}", shortcode)
+        };
+        let codepoints = inspect(emoji).sequence;
+
+        AlfredMods {
+            cmd: AlfredMod::new(shortcode, shortcode_subtitle),
+            alt: AlfredMod::new(codepoints.clone(), format!("Copy {}", codepoints)),
+            ctrl: AlfredMod::new(
+                copied.to_string(),
+                "Copy without a trailing space".to_string(),
+            ),
+            shift: AlfredMod::new(format!("{} ", copied), describe_line(emoji)),
+        }
+    }
+}
+
+/// Every skin tone of every gender form of an emoji, starting with its default look
+fn variants(emoji: &'static Emoji) -> Vec<&'static Emoji> {
+    let base = emoji.with_skin_tone(SkinTone::Default).unwrap_or(emoji);
+    let genders: Vec<&'static Emoji> = describe(base.as_str())
+        .into_iter()
+        .flatten()
+        .flat_map(|description| description.gender_variants)
+        .filter_map(emojis::get)
+        .collect();
+    let forms = if genders.is_empty() {
+        vec![base]
+    } else {
+        genders
+    };
+
+    forms
+        .into_iter()
+        .flat_map(|form| match form.skin_tones() {
+            Some(tones) => tones.collect(),
+            None => vec![form],
+        })
+        .collect()
+}
+
+/// The name followed by the primary shortcode and group, e.g. "fire · :fire: · travel-and-places"
+fn subtitle(name: &str, unicode: Option<&'static Emoji>) -> String {
+    let mut parts = vec![name.to_string()];
+    if let Some(emoji) = unicode {
+        if let Some(shortcode) = emoji.shortcode() {
+            parts.push(format!(":{}:", shortcode));
+        }
+        parts.push(group_name(emoji.group()).to_string());
+    }
+    parts.join(" · ")
+}
+
+/// A slang term with its category, e.g. "\"bussin\" (gen-z slang)"
+fn slang_meaning(term: &str) -> String {
+    match SLANG_MAP.get(term) {
+        Some(slang) => format!("\"{}\" ({} slang)", term, slang.category),
+        None => format!("\"{}\" (slang)", term),
+    }
+}
+
+/// The copied text followed by a description of every emoji in it
+fn largetype(emoji: &str, copied: &str) -> String {
+    let descriptions: Vec<String> = describe(emoji)
+        .into_iter()
+        .flatten()
+        .map(|description| full_description(&description))
+        .collect();
+    if descriptions.is_empty() {
+        return copied.to_string();
+    }
+    format!("{}\n\n{}", copied, descriptions.join("\n\n"))
+}
+
+/// Every detail of an emoji on its own line
+fn full_description(description: &Description) -> String {
+    let mut lines = vec![
+        format!("{} {}", description.emoji, description.name),
+        match description.subgroup {
+            Some(subgroup) => format!("{} › {}", description.group, subgroup),
+            None => description.group.to_string(),
+        },
+        format!("Unicode {}", description.unicode_version),
+    ];
+    if !description.shortcodes.is_empty() {
+        let shortcodes = description
+            .shortcodes
+            .iter()
+            .map(|code| format!(":{}:", code));
+        lines.push(shortcodes.collect::<Vec<_>>().join(" "));
+    }
+    if !description.slang.is_empty() {
+        let terms = description.slang.iter().map(|slang| slang.term);
+        lines.push(format!("Slang: {}", terms.collect::<Vec<_>>().join(", ")));
+    }
+    lines.join("\n")
+}
+
+/// The Emojipedia page of an emoji, which redirects from the percent-encoded emoji
+fn emojipedia_url(emoji: &str) -> String {
+    let encoded: String = emoji.bytes().map(|byte| format!("%{:02X}", byte)).collect();
+    format!("https://emojipedia.org/{}", encoded)
+}
+
+/// Lowercase hex codepoints joined by dashes, e.g. "1f9d1-1f3fd-200d-1f4bb"
+fn uid(emoji: &str) -> String {
+    emoji
+        .chars()
+        .map(|c| format!("{:x}", c as u32))
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// A one-line description of the emoji, or the names of every emoji in a combo
+fn describe_line(text: &str) -> String {
+    let descriptions: Vec<_> = describe(text).into_iter().flatten().collect();
+    match descriptions.as_slice() {
+        [description] => {
+            let mut parts = vec![description.name.to_string()];
+            parts.push(match description.subgroup {
+                Some(subgroup) => format!("{} › {}", description.group, subgroup),
+                None => description.group.to_string(),
+            });
+            if !description.shortcodes.is_empty() {
+                let shortcodes = description
+                    .shortcodes
+                    .iter()
+                    .map(|code| format!(":{}:", code));
+                parts.push(shortcodes.collect::<Vec<_>>().join(" "));
+            }
+            parts.push(format!("Unicode {}", description.unicode_version));
+            parts.join(" · ")
+        }
+        descriptions => descriptions
+            .iter()
+            .map(|description| description.name)
+            .collect::<Vec<_>>()
+            .join(" + "),
+    }
+}
+
+impl AlfredResponse {
+    /// Build an Alfred response listing the search results
+    pub fn from_results(results: Vec<SearchResult>, format: Option<EscapeFormat>) -> Self {
+        AlfredResponse {
+            items: results
+                .into_iter()
+                .map(|result| AlfredItem::from_result(result, format))
+                .collect(),
+            skipknowledge: false,
+        }
+    }
+
+    /// List the skin tones and genders of a query that is a single emoji having them
+    ///
+    /// This is what Alfred runs when an item's `autocomplete` is taken with Tab.
+    pub fn variants(query: &str, format: Option<EscapeFormat>) -> Option<Self> {
+        let variants = variants(emojis::get(query.trim())?);
+        if variants.len() < 2 {
+            return None;
+        }
+
+        Some(AlfredResponse {
+            items: variants
+                .into_iter()
+                .map(|emoji| {
+                    let base = emoji.with_skin_tone(SkinTone::Default).unwrap_or(emoji);
+                    AlfredItem::new(
+                        emoji.as_str(),
+                        subtitle(emoji.name(), Some(base)),
+                        Some(base),
+                        format,
+                    )
+                })
+                .collect(),
+            skipknowledge: false,
+        })
+    }
+
+    /// Placeholder response shown when nothing matched
+    pub fn no_results() -> Self {
+        AlfredResponse {
+            items: vec![AlfredItem {
+                uid: "no-results".to_string(),
+                title: "No emojis found".to_string(),
+                subtitle: "Try a different search term".to_string(),
+                arg: "".to_string(),
+                text: AlfredItemText {
+                    copy: "".to_string(),
+                    largetype: "".to_string(),
+                },
+                valid: false,
+                quicklookurl: None,
+                autocomplete: None,
+                mods: None,
+                variables: None,
+            }],
+            skipknowledge: false,
+        }
+    }
+
+    /// Create a response explaining why the query could not be parsed
+    pub fn invalid_query(err: &QueryError) -> Self {
+        AlfredResponse {
+            items: vec![AlfredItem {
+                uid: "invalid-query".to_string(),
+                title: "Invalid query".to_string(),
+                subtitle: err.to_string(),
+                arg: "".to_string(),
+                text: AlfredItemText {
+                    copy: "".to_string(),
+                    largetype: "".to_string(),
+                },
+                valid: false,
+                quicklookurl: None,
+                autocomplete: None,
+                mods: None,
+                variables: None,
+            }],
+            skipknowledge: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::{SearchOptions, Searcher};
+    use crate::variants::SkinTone as Tone;
+
+    fn item(query: &str, emoji: &str) -> AlfredItem {
+        let result = Searcher::default()
+            .search(query)
+            .into_iter()
+            .find(|result| result.emoji == emoji)
+            .unwrap();
+        AlfredItem::from_result(result, None)
+    }
+
+    #[test]
+    fn test_modifier_actions() {
+        let mods = item("fire", "🔥").mods.unwrap();
+        assert_eq!((mods.cmd.arg.as_str(), mods.cmd.valid), (":fire:", true));
+        assert_eq!(mods.alt.arg, "U+1F525");
+        assert_eq!(mods.ctrl.arg, "🔥");
+        assert_eq!(mods.shift.arg, "🔥 ");
+        assert!(mods.shift.subtitle.starts_with("fire · travel-and-places"));
+    }
+
+    #[test]
+    fn test_modifier_without_shortcode() {
+        let mods = item("slay", "💅👑").mods.unwrap();
+        assert_eq!(mods.cmd.arg, "");
+        assert!(!mods.cmd.valid);
+        assert_eq!(mods.alt.arg, "U+1F485 U+1F451");
+        assert_eq!(mods.shift.subtitle, "nail polish + crown");
+    }
+
+    #[test]
+    fn test_modifier_actions_with_escape() {
+        let result = Searcher::default().search("fire").remove(0);
+        let item = AlfredItem::from_result(result, Some(EscapeFormat::Rust));
+        assert_eq!(item.arg, "\"\\u{1F525}\" ");
+        let mods = item.mods.unwrap();
+        assert_eq!(mods.ctrl.arg, "\"\\u{1F525}\"");
+        // The codepoints and shortcode are still those of the emoji
+        assert_eq!(mods.alt.arg, "U+1F525");
+        assert_eq!(mods.cmd.arg, ":fire:");
+    }
+
+    #[test]
+    fn test_uids_are_unique_per_emoji() {
+        let results = Searcher::default().search("weed");
+        let response = AlfredResponse::from_results(results, None);
+        let weed: Vec<&str> = response
+            .items
+            .iter()
+            .take(3)
+            .map(|item| item.uid.as_str())
+            .collect();
+        assert_eq!(weed, vec!["1f966", "1f33f", "1f343"]);
+
+        let uids: std::collections::HashSet<&str> = response
+            .items
+            .iter()
+            .map(|item| item.uid.as_str())
+            .collect();
+        assert_eq!(uids.len(), response.items.len());
+    }
+
+    #[test]
+    fn test_uids_distinguish_variants() {
+        let toned = Searcher::new(SearchOptions {
+            skin_tone: Some(Tone::Medium),
+            ..Default::default()
+        });
+        let default =
+            AlfredItem::from_result(Searcher::default().search("thumbsup").remove(0), None);
+        let medium = AlfredItem::from_result(toned.search("thumbsup").remove(0), None);
+        assert_eq!(default.uid, "1f44d");
+        assert_eq!(medium.uid, "1f44d-1f3fd");
+
+        // The uid follows the emoji, not how it is copied or which word found it
+        let escaped = AlfredItem::from_result(
+            Searcher::default().search("+1").remove(0),
+            Some(EscapeFormat::Html),
+        );
+        assert_eq!(escaped.uid, default.uid);
+    }
+
+    #[test]
+    fn test_skipknowledge_only_when_set() {
+        let mut response = AlfredResponse::from_results(Searcher::default().search("fire"), None);
+        let json = serde_json::to_value(&response).unwrap();
+        assert!(json.get("skipknowledge").is_none());
+
+        response.skipknowledge = true;
+        let json = serde_json::to_value(&response).unwrap();
+        assert_eq!(json["skipknowledge"], true);
+    }
+
+    #[test]
+    fn test_variants_list() {
+        let wave = AlfredResponse::variants("👋", None).unwrap();
+        let titles: Vec<&str> = wave.items.iter().map(|item| item.title.as_str()).collect();
+        assert_eq!(titles, vec!["👋", "👋🏻", "👋🏼", "👋🏽", "👋🏾", "👋🏿"]);
+
+        let mage = AlfredResponse::variants("🧙", None).unwrap();
+        assert_eq!(mage.items.len(), 18);
+        assert_eq!(mage.items[6].title, "🧙‍♂️");
+        assert_eq!(mage.items[17].title, "🧙🏿‍♀️");
+
+        // A toned emoji lists the same variants, and plain queries are searched
+        assert_eq!(AlfredResponse::variants("👋🏽", None).unwrap().items.len(), 6);
+        assert!(AlfredResponse::variants("🔥", None).is_none());
+        assert!(AlfredResponse::variants("wave", None).is_none());
+    }
+
+    #[test]
+    fn test_autocomplete_only_with_variants() {
+        assert_eq!(item("wave", "👋").autocomplete.as_deref(), Some("👋"));
+        assert_eq!(item("mage", "🧙").autocomplete.as_deref(), Some("🧙"));
+        assert_eq!(item("fire", "🔥").autocomplete, None);
+        assert_eq!(item("slay", "💅👑").autocomplete, None);
+    }
+
+    #[test]
+    fn test_subtitles() {
+        assert_eq!(
+            item("cold face", "🥶").subtitle,
+            "cold face · :cold_face: · smileys-and-emotion"
+        );
+        assert_eq!(
+            item("bussin", "😋").subtitle,
+            "\"bussin\" (gen-z slang) · face savoring food · :yum: · smileys-and-emotion"
+        );
+        // Combos have no shortcode or group of their own
+        assert_eq!(
+            item("slay", "💅👑").subtitle,
+            "\"slay\" (gen-z slang) · nail polish + crown"
+        );
+        assert_eq!(slang_meaning("not a term"), "\"not a term\" (slang)");
+    }
+
+    #[test]
+    fn test_subtitle_names_the_skin_tone() {
+        let toned = Searcher::new(SearchOptions {
+            skin_tone: Some(Tone::Dark),
+            ..Default::default()
+        });
+        let item = AlfredItem::from_result(toned.search("wave").remove(0), None);
+        assert_eq!(
+            item.subtitle,
+            "waving hand: dark skin tone · :wave: · people-and-body"
+        );
+    }
+
+    #[test]
+    fn test_largetype_and_quicklook() {
+        let fire = item("fire", "🔥");
+        let lines: Vec<&str> = fire.text.largetype.lines().collect();
+        assert_eq!(
+            lines[..6],
+            [
+                "🔥",
+                "",
+                "🔥 fire",
+                "travel-and-places › sky-and-weather",
+                "Unicode 0.6",
+                ":fire:"
+            ]
+        );
+        assert!(lines[6].starts_with("Slang: ") && lines[6].contains("lit"));
+        assert_eq!(
+            fire.quicklookurl.as_deref(),
+            Some("https://emojipedia.org/%F0%9F%94%A5")
+        );
+
+        let combo = item("slay", "💅👑");
+        assert!(combo.text.largetype.contains("💅 nail polish"));
+        assert!(combo.text.largetype.contains("\n\n👑 crown"));
+        assert_eq!(combo.quicklookurl, None);
+    }
+}
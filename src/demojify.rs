@@ -0,0 +1,152 @@
+use emojis::{Emoji, SkinTone};
+
+/// The longest emoji in the database, in characters (e.g. "👩🏻‍❤️‍💋‍👨🏼")
+const MAX_EMOJI_CHARS: usize = 10;
+
+/// What [`demojify`] replaces each emoji with
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum DemojifyStyle {
+    /// The first shortcode, e.g. `:fire:` or `:wave::skin-tone-4:`, or the name in brackets if
+    /// there is none
+    #[default]
+    Shortcode,
+    /// The CLDR name in brackets, e.g. `[fire]`
+    Name,
+    /// The CLDR name as a plain phrase, e.g. `thumbs up, medium skin tone`
+    Alt,
+}
+
+/// Replace every emoji in the text, including ZWJ sequences and skin tones
+///
+/// Symbols that are plain text unless followed by U+FE0F (e.g. ©, ™, ↔) are kept as they are.
+pub fn demojify(text: &str, style: DemojifyStyle) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        if let Some((emoji, length)) = emoji_at(rest) {
+            output.push_str(&replacement(emoji, style));
+            rest = &rest[length..];
+        } else {
+            output.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    output
+}
+
+/// The longest emoji starting the text, with its length in bytes
+fn emoji_at(text: &str) -> Option<(&'static Emoji, usize)> {
+    let mut chars = text.chars();
+    let first = chars.next()?;
+    // Only keycaps like "#️⃣" start with ASCII
+    if first.is_ascii() && !matches!(chars.next(), Some('\u{FE0F}' | '\u{20E3}')) {
+        return None;
+    }
+
+    let (emoji, end) = text
+        .char_indices()
+        .take(MAX_EMOJI_CHARS)
+        .map(|(i, c)| i + c.len_utf8())
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .find_map(|end| emojis::get(&text[..end]).map(|emoji| (emoji, end)))?;
+
+    let is_text_symbol = end == first.len_utf8() && emoji.as_str().ends_with('\u{FE0F}');
+    (!is_text_symbol).then_some((emoji, end))
+}
+
+/// The text an emoji is replaced with
+fn replacement(emoji: &Emoji, style: DemojifyStyle) -> String {
+    match style {
+        DemojifyStyle::Shortcode => {
+            // Skin tone variants have no shortcodes of their own, so use the base's with
+            // Slack's tone suffix
+            let base = emoji.with_skin_tone(SkinTone::Default).unwrap_or(emoji);
+            let suffix = match emoji.skin_tone() {
+                None | Some(SkinTone::Default) => Some(String::new()),
+                Some(tone) => slack_tone(tone).map(|n| format!(":skin-tone-{}:", n)),
+            };
+            match (base.shortcode(), suffix) {
+                (Some(shortcode), Some(suffix)) => format!(":{}:{}", shortcode, suffix),
+                _ => format!("[{}]", emoji.name()),
+            }
+        }
+        DemojifyStyle::Name => format!("[{}]", emoji.name()),
+        DemojifyStyle::Alt => emoji.name().replace(": ", ", "),
+    }
+}
+
+/// The number of a skin tone in Slack's `:skin-tone-N:` suffix, none for mixed tones
+fn slack_tone(tone: SkinTone) -> Option<u8> {
+    match tone {
+        SkinTone::Light => Some(2),
+        SkinTone::MediumLight => Some(3),
+        SkinTone::Medium => Some(4),
+        SkinTone::MediumDark => Some(5),
+        SkinTone::Dark => Some(6),
+        _ => None,
+    }
+}
+
+/// The skin tone of a Slack `:skin-tone-N:` suffix number, the inverse of [`slack_tone`]
+pub(crate) fn from_slack_tone(number: char) -> Option<SkinTone> {
+    match number {
+        '2' => Some(SkinTone::Light),
+        '3' => Some(SkinTone::MediumLight),
+        '4' => Some(SkinTone::Medium),
+        '5' => Some(SkinTone::MediumDark),
+        '6' => Some(SkinTone::Dark),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_styles() {
+        let text = "Ship it 🚀🔥!";
+        assert_eq!(
+            demojify(text, DemojifyStyle::Shortcode),
+            "Ship it :rocket::fire:!"
+        );
+        assert_eq!(
+            demojify(text, DemojifyStyle::Name),
+            "Ship it [rocket][fire]!"
+        );
+        assert_eq!(
+            demojify("👍🏽", DemojifyStyle::Alt),
+            "thumbs up, medium skin tone"
+        );
+    }
+
+    #[test]
+    fn test_sequences() {
+        assert_eq!(
+            demojify("👩🏻‍❤️‍💋‍👨🏼 👨‍👩‍👧", DemojifyStyle::Shortcode),
+            "[kiss: woman, man, light skin tone, medium-light skin tone] :family_man_woman_girl:"
+        );
+        assert_eq!(
+            demojify("👋🏽 👍🏿 🧑🏻‍💻", DemojifyStyle::Shortcode),
+            ":wave::skin-tone-4: :+1::skin-tone-6: :technologist::skin-tone-2:"
+        );
+        assert_eq!(
+            demojify("#️⃣ 🇯🇵", DemojifyStyle::Name),
+            "[keycap: #] [flag: Japan]"
+        );
+    }
+
+    #[test]
+    fn test_plain_text_is_kept() {
+        let text = "# Notes © 2024 ™ a↔b, ❤️ é";
+        assert_eq!(
+            demojify(text, DemojifyStyle::Shortcode),
+            "# Notes © 2024 ™ a↔b, :heart: é"
+        );
+    }
+}
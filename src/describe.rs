@@ -0,0 +1,292 @@
+use crate::facet::{group_name, subgroup_of};
+use crate::index::INDEX;
+use crate::listing::fzf_emoji;
+use crate::model::EmojiMatch;
+use crate::output::match_name;
+use crate::slang::SLANG_MAP;
+use crate::variants::split_sequence;
+use emojis::{Emoji, SkinTone};
+use serde::Serialize;
+
+/// Everything known about a single emoji, for decoding what someone sent
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Description {
+    /// The emoji as given, including any skin tone
+    pub emoji: String,
+    /// The Unicode CLDR name
+    pub name: &'static str,
+    /// Kebab-case Unicode group, e.g. "smileys-and-emotion"
+    pub group: &'static str,
+    /// Unicode subgroup, e.g. "face-smiling"
+    pub subgroup: Option<&'static str>,
+    /// Unicode version the emoji was introduced in, e.g. "14.0"
+    pub unicode_version: String,
+    /// GitHub/Slack style shortcodes of the default skin tone, without colons
+    pub shortcodes: Vec<&'static str>,
+    /// The emoji in every skin tone, if it supports them
+    pub skin_tones: Vec<&'static str>,
+    /// The person, man and woman forms of the emoji, if it has them
+    pub gender_variants: Vec<&'static str>,
+    /// Slang terms mapping to the emoji, alone or within a combo
+    pub slang: Vec<SlangUse>,
+}
+
+/// A slang term that maps to a described emoji
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SlangUse {
+    pub term: &'static str,
+    /// Category the term is listed under in `data/slang.toml`
+    pub category: &'static str,
+    /// The whole combo, if the emoji is only part of the term's mapping (e.g. "💅👑")
+    pub combo: Option<&'static str>,
+}
+
+/// A slang combo and every term mapping to it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Combo {
+    /// The combo as listed in `data/slang.toml`, e.g. "💅👑"
+    pub combo: &'static str,
+    /// The names of its emojis joined by " + "
+    pub name: String,
+    pub slang: Vec<&'static str>,
+}
+
+/// What `almoji preview` shows for an `almoji --fzf` line
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Preview {
+    /// The emoji field of the line
+    pub emoji: String,
+    /// The slang combo the emojis make up, if they are one
+    pub combo: Option<Combo>,
+    /// Every emoji of the line, described
+    pub descriptions: Vec<Description>,
+}
+
+/// Describe the emoji of an fzf line and the slang combo it may be
+pub fn preview(line: &str) -> Preview {
+    let emoji = fzf_emoji(line);
+    let descriptions: Vec<Description> = describe(emoji).into_iter().flatten().collect();
+    let combo = if descriptions.len() > 1 {
+        find_combo(emoji)
+    } else {
+        None
+    };
+
+    Preview {
+        emoji: emoji.to_string(),
+        combo,
+        descriptions,
+    }
+}
+
+/// The slang combo made of the emojis, ignoring skin tones
+fn find_combo(text: &str) -> Option<Combo> {
+    let base: String = text
+        .chars()
+        .filter(|c| !('\u{1F3FB}'..='\u{1F3FF}').contains(c))
+        .collect();
+
+    let mut found: Option<Combo> = None;
+    for entry in INDEX.slang {
+        for (value, matched) in entry.emojis() {
+            if !matches!(matched, EmojiMatch::Sequence(_)) || value != base {
+                continue;
+            }
+            let combo = found.get_or_insert_with(|| Combo {
+                combo: value,
+                name: match_name(&matched),
+                slang: Vec::new(),
+            });
+            if !combo.slang.contains(&entry.term) {
+                combo.slang.push(entry.term);
+            }
+        }
+    }
+    found
+}
+
+/// Describe every emoji in the text
+///
+/// Each run of text that is not a known emoji is returned trimmed as one `Err`, skipping
+/// whitespace.
+pub fn describe(text: &str) -> Vec<Result<Description, &str>> {
+    split_sequence(text)
+        .into_iter()
+        .filter(|part| !matches!(part, Err(text) if text.trim().is_empty()))
+        .map(|part| part.map(describe_emoji).map_err(str::trim))
+        .collect()
+}
+
+/// Describe a single emoji from the database
+fn describe_emoji(emoji: &'static Emoji) -> Description {
+    let base = emoji.with_skin_tone(SkinTone::Default).unwrap_or(emoji);
+    let version = emoji.unicode_version();
+
+    Description {
+        emoji: emoji.as_str().to_string(),
+        name: emoji.name(),
+        group: group_name(emoji.group()),
+        subgroup: subgroup_of(emoji),
+        unicode_version: format!("{}.{}", version.major(), version.minor()),
+        shortcodes: base.shortcodes().collect(),
+        skin_tones: base
+            .skin_tones()
+            .map(|tones| tones.map(Emoji::as_str).collect())
+            .unwrap_or_default(),
+        gender_variants: gender_variants(base),
+        slang: slang_uses(base),
+    }
+}
+
+/// The person, man and woman forms of an emoji, found by name (e.g. "man mage")
+fn gender_variants(emoji: &Emoji) -> Vec<&'static str> {
+    let name = emoji.name();
+    let rest = ["person ", "man ", "woman "]
+        .iter()
+        .find_map(|prefix| name.strip_prefix(prefix))
+        .unwrap_or(name);
+
+    let names = [
+        rest.to_string(),
+        format!("person {}", rest),
+        format!("man {}", rest),
+        format!("woman {}", rest),
+    ];
+    let variants: Vec<&'static str> = names
+        .iter()
+        .filter_map(|name| {
+            let name = name.to_lowercase();
+            INDEX
+                .emoji_exact(&name)
+                .find(|entry| entry.name == name)
+                .map(|entry| entry.text)
+        })
+        .collect();
+
+    // A lone form is not a variant of anything
+    if variants.len() > 1 {
+        variants
+    } else {
+        Vec::new()
+    }
+}
+
+/// Slang terms whose mapping is or contains the emoji, in alphabetical order
+fn slang_uses(emoji: &Emoji) -> Vec<SlangUse> {
+    let mut uses = Vec::new();
+
+    for entry in INDEX.slang {
+        let category = SLANG_MAP
+            .get(entry.term)
+            .map(|slang| slang.category)
+            .unwrap_or_default();
+
+        for value in entry.values {
+            let parts = split_sequence(value);
+            let is_part = |part: &Result<&Emoji, &str>| {
+                part.is_ok_and(|p| p.with_skin_tone(SkinTone::Default).unwrap_or(p) == emoji)
+            };
+
+            if parts.iter().any(is_part) {
+                uses.push(SlangUse {
+                    term: entry.term,
+                    category,
+                    combo: (parts.len() > 1).then_some(*value),
+                });
+                break;
+            }
+        }
+    }
+
+    uses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn describe_one(text: &str) -> Description {
+        describe(text).remove(0).unwrap()
+    }
+
+    #[test]
+    fn test_describe_emoji() {
+        let melting = describe_one("🫠");
+        assert_eq!(melting.name, "melting face");
+        assert_eq!(melting.group, "smileys-and-emotion");
+        assert_eq!(melting.subgroup, Some("face-smiling"));
+        assert_eq!(melting.unicode_version, "14.0");
+        assert_eq!(melting.shortcodes, vec!["melting_face"]);
+        assert!(melting.skin_tones.is_empty());
+    }
+
+    #[test]
+    fn test_describe_variants() {
+        let wave = describe_one("👋🏽");
+        assert_eq!(wave.emoji, "👋🏽");
+        assert_eq!(wave.skin_tones.len(), 6);
+        assert_eq!(wave.shortcodes, vec!["wave"]);
+
+        let mage = describe_one("🧙‍♀️");
+        assert_eq!(mage.gender_variants, vec!["🧙", "🧙‍♂️", "🧙‍♀️"]);
+    }
+
+    #[test]
+    fn test_describe_slang() {
+        let nails = describe_one("💅");
+        let slay = nails.slang.iter().find(|s| s.term == "slay").unwrap();
+        assert_eq!((slay.category, slay.combo), ("gen-z", None));
+        let girlboss = nails.slang.iter().find(|s| s.term == "girlboss").unwrap();
+        assert_eq!(girlboss.combo, Some("💅👩‍💼"));
+        assert!(describe_one("🔥").slang.iter().any(|s| s.term == "fire"));
+    }
+
+    #[test]
+    fn test_preview_combo_line() {
+        let preview = preview("💅👑\tnail polish + crown\tcunt slay");
+        assert_eq!(preview.emoji, "💅👑");
+        assert_eq!(preview.descriptions.len(), 2);
+        let combo = preview.combo.unwrap();
+        assert_eq!(combo.name, "nail polish + crown");
+        assert!(combo.slang.contains(&"cunt"));
+
+        // Skin tones rendered into the combo are ignored
+        assert!(super::preview("💅🏽👑").combo.is_some());
+    }
+
+    #[test]
+    fn test_preview_single_emoji_line() {
+        let preview = preview("🔥\tfire\t:fire: lit");
+        assert_eq!(preview.emoji, "🔥");
+        assert_eq!(preview.combo, None);
+        assert_eq!(preview.descriptions[0].name, "fire");
+        assert!(super::preview("nope").descriptions.is_empty());
+    }
+
+    #[test]
+    fn test_describe_multiple() {
+        let parts = describe("🔥 x💅");
+        assert_eq!(parts.len(), 3);
+        assert!(parts[0].is_ok());
+        assert_eq!(parts[1], Err("x"));
+    }
+
+    #[test]
+    fn test_describe_text_runs() {
+        let parts = describe("hello 🔥 big world🦀!?");
+        let names: Vec<Result<&str, &str>> = parts
+            .iter()
+            .map(|part| part.as_ref().map(|d| d.name).map_err(|text| *text))
+            .collect();
+        assert_eq!(
+            names,
+            [
+                Err("hello"),
+                Ok("fire"),
+                Err("big world"),
+                Ok("crab"),
+                Err("!?")
+            ]
+        );
+    }
+}
@@ -0,0 +1,288 @@
+use crate::demojify::from_slack_tone;
+use crate::slang::SLANG_MAP;
+use crate::variants::{apply_skin_tone, split_sequence, SkinTone};
+
+/// Slang terms can span this many words (e.g. "stable diffusion")
+const MAX_SLANG_WORDS: usize = 3;
+
+/// Options for [`emojify`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EmojifyOptions {
+    /// Skin tone applied to emojis that support it
+    pub skin_tone: Option<SkinTone>,
+    /// Also replace whole-word slang terms (e.g. "lit" -> 🔥)
+    pub slang: bool,
+    /// Leave Markdown code spans and fenced code blocks untouched
+    pub markdown: bool,
+}
+
+/// Replace `:shortcode:` tokens, and optionally slang terms, with emojis
+///
+/// Shortcodes are looked up in the emoji database first, then in the slang table.
+/// Unknown shortcodes are left as they are.
+pub fn emojify(text: &str, options: &EmojifyOptions) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut fence: Option<&str> = None;
+
+    for line in text.split_inclusive('\n') {
+        if !options.markdown {
+            emojify_text(line, options, &mut output);
+            continue;
+        }
+
+        // Fenced code blocks are closed by the same kind of fence
+        let marker = code_fence(line);
+        match (fence, marker) {
+            (Some(open), Some(close)) if close.starts_with(open) => {
+                fence = None;
+                output.push_str(line);
+            }
+            (Some(_), _) => output.push_str(line),
+            (None, Some(open)) => {
+                fence = Some(open);
+                output.push_str(line);
+            }
+            (None, None) => emojify_markdown_line(line, options, &mut output),
+        }
+    }
+
+    output
+}
+
+/// The backtick or tilde run opening a fenced code block, if the line is a fence
+fn code_fence(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start_matches(' ');
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let fence_char = trimmed.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let length = trimmed.len() - trimmed.trim_start_matches(fence_char).len();
+    (length >= 3).then(|| &trimmed[..length])
+}
+
+/// Emojify a Markdown line outside a fenced block, skipping `code` spans
+fn emojify_markdown_line(line: &str, options: &EmojifyOptions, output: &mut String) {
+    let mut rest = line;
+
+    while let Some(start) = rest.find('`') {
+        let ticks = rest[start..].len() - rest[start..].trim_start_matches('`').len();
+        let opening = &rest[start..start + ticks];
+        let after = &rest[start + ticks..];
+
+        // A span closes at the next run of exactly as many backticks
+        let close = after
+            .match_indices(opening)
+            .find(|(i, _)| !after[..*i].ends_with('`') && !after[i + ticks..].starts_with('`'));
+        let Some((close, _)) = close else {
+            emojify_text(&rest[..start + ticks], options, output);
+            rest = after;
+            continue;
+        };
+
+        emojify_text(&rest[..start], options, output);
+        let end = start + ticks + close + ticks;
+        output.push_str(&rest[start..end]);
+        rest = &rest[end..];
+    }
+
+    emojify_text(rest, options, output);
+}
+
+/// Emojify plain text
+fn emojify_text(text: &str, options: &EmojifyOptions, output: &mut String) {
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        if c == ':' {
+            if let Some((emoji, length)) = shortcode_at(rest) {
+                match toned_at(emoji, &rest[length..]) {
+                    Some((toned, suffix)) => {
+                        output.push_str(toned);
+                        rest = &rest[length + suffix..];
+                    }
+                    None => {
+                        output.push_str(&render(emoji, options.skin_tone));
+                        rest = &rest[length..];
+                    }
+                }
+                continue;
+            }
+        } else if options.slang && c.is_alphanumeric() && !ends_with_word(output) {
+            if let Some((emoji, length)) = slang_at(rest) {
+                output.push_str(&render(emoji, options.skin_tone));
+                rest = &rest[length..];
+                continue;
+            }
+            // Copy the whole word so slang never matches inside it
+            let length = rest.find(|c| !is_word_char(c)).unwrap_or(rest.len());
+            output.push_str(&rest[..length]);
+            rest = &rest[length..];
+            continue;
+        }
+
+        output.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+}
+
+/// The emoji for a `:shortcode:` at the start of the text, with the token length
+fn shortcode_at(text: &str) -> Option<(&'static str, usize)> {
+    let body = &text[1..];
+    let length = body.find(|c: char| !is_shortcode_char(c))?;
+    if length == 0 || !body[length..].starts_with(':') {
+        return None;
+    }
+
+    let shortcode = &body[..length];
+    let emoji = emojis::get_by_shortcode(shortcode)
+        .map(|emoji| emoji.as_str())
+        .or_else(|| {
+            SLANG_MAP
+                .get(shortcode.to_lowercase().as_str())
+                .and_then(|slang| slang.emojis.first().copied())
+        })?;
+    Some((emoji, length + 2))
+}
+
+/// The emoji in the skin tone of a Slack `:skin-tone-N:` suffix starting the text, with the
+/// suffix length
+///
+/// This reads back what `demojify` writes for skin tone variants, e.g. `:wave::skin-tone-4:`.
+fn toned_at(emoji: &str, text: &str) -> Option<(&'static str, usize)> {
+    let suffix = text.strip_prefix(":skin-tone-")?;
+    let mut chars = suffix.chars();
+    let tone = from_slack_tone(chars.next()?)?;
+    if chars.next() != Some(':') {
+        return None;
+    }
+    let toned = emojis::get(emoji)?.with_skin_tone(tone)?;
+    Some((toned.as_str(), ":skin-tone-N:".len()))
+}
+
+/// The emoji for the longest slang term starting the text, with the term length
+///
+/// Terms must end at a word boundary, and may span a few words separated by single spaces.
+fn slang_at(text: &str) -> Option<(&'static str, usize)> {
+    let mut ends = Vec::new();
+    let mut end = 0;
+
+    while ends.len() < MAX_SLANG_WORDS {
+        end += text[end..]
+            .find(|c| !is_word_char(c))
+            .unwrap_or(text.len() - end);
+        ends.push(end);
+        match text[end..].strip_prefix(' ') {
+            Some(next) if next.starts_with(is_word_char) => end += 1,
+            _ => break,
+        }
+    }
+
+    ends.into_iter().rev().find_map(|end| {
+        // Quotes and dashes around a word are punctuation, not part of the term
+        let term = text[..end].trim_end_matches(['\'', '-']);
+        let slang = SLANG_MAP.get(term.to_lowercase().as_str())?;
+        Some((*slang.emojis.first()?, term.len()))
+    })
+}
+
+/// Characters that can appear in a shortcode, e.g. `+1` or `e-mail`
+fn is_shortcode_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '+')
+}
+
+/// Characters that can appear in a slang term, e.g. `c++`, `gpt-4` or `y'all`
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '+' | '\'')
+}
+
+/// Whether the output so far ends inside a word, where an opening quote does not count
+fn ends_with_word(output: &str) -> bool {
+    output
+        .chars()
+        .next_back()
+        .is_some_and(|c| is_word_char(c) && c != '\'')
+}
+
+/// Apply the skin tone to each emoji of a value that supports it
+fn render(value: &str, skin_tone: Option<SkinTone>) -> String {
+    let Some(skin_tone) = skin_tone else {
+        return value.to_string();
+    };
+    split_sequence(value)
+        .into_iter()
+        .map(|part| match part {
+            Ok(emoji) => apply_skin_tone(emoji, &skin_tone),
+            Err(text) => text.to_string(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::demojify::{demojify, DemojifyStyle};
+
+    #[test]
+    fn test_shortcodes() {
+        let options = EmojifyOptions::default();
+        assert_eq!(
+            emojify("Shipped :rocket: :+1: :nope: 10:30:00", &options),
+            "Shipped 🚀 👍 :nope: 10:30:00"
+        );
+        assert_eq!(emojify("::tada::", &options), ":🎉:");
+    }
+
+    #[test]
+    fn test_skin_tone() {
+        let options = EmojifyOptions {
+            skin_tone: Some(SkinTone::Medium),
+            ..Default::default()
+        };
+        assert_eq!(emojify(":wave: :rocket:", &options), "👋🏽 🚀");
+        // An explicit Slack tone suffix wins over the option
+        assert_eq!(emojify(":wave::skin-tone-6:", &options), "👋🏿");
+        assert_eq!(
+            emojify(":rocket::skin-tone-2: :wave::skin-tone-7:", &options),
+            "🚀:skin-tone-2: 👋🏽:skin-tone-7:"
+        );
+    }
+
+    #[test]
+    fn test_demojify_round_trip() {
+        let text = "hi 👋🏽 👍🏿 🧑🏻‍💻 ✌🏼 👩🏾‍🚀 🚀 ❤️";
+        let shortcodes = demojify(text, DemojifyStyle::Shortcode);
+        assert_eq!(emojify(&shortcodes, &EmojifyOptions::default()), text);
+    }
+
+    #[test]
+    fn test_slang_words() {
+        let options = EmojifyOptions {
+            slang: true,
+            ..Default::default()
+        };
+        assert_eq!(emojify("this is lit, cap", &options), "this is 🔥, 🧢");
+        assert_eq!(
+            emojify("Lit! capital 'lit' unlit", &options),
+            "🔥! capital '🔥' unlit"
+        );
+        assert_eq!(
+            emojify("Stable  Diffusion or stable diffusion", &options),
+            "Stable  Diffusion or 🎨"
+        );
+        assert_eq!(emojify("lit", &EmojifyOptions::default()), "lit");
+    }
+
+    #[test]
+    fn test_markdown_code() {
+        let options = EmojifyOptions {
+            markdown: true,
+            ..Default::default()
+        };
+        let text = ":tada: `a :tada: b` ``:x:``\n```\n:tada:\n```\n:tada:\n";
+        assert_eq!(
+            emojify(text, &options),
+            "🎉 `a :tada: b` ``:x:``\n```\n:tada:\n```\n🎉\n"
+        );
+        assert_eq!(emojify("`:tada:", &options), "`🎉");
+    }
+}
@@ -0,0 +1,93 @@
+/// Source code escape syntax for [`escape`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum EscapeFormat {
+    /// `"\u{1F525}"`
+    Rust,
+    /// `"\uD83D\uDD25"` (UTF-16 surrogate pairs)
+    Js,
+    /// `"\U0001F525"`
+    Python,
+    /// `"\uD83D\uDD25"` (UTF-16 surrogate pairs)
+    Java,
+    /// `&#x1F525;`
+    Html,
+    /// `"\01F525"`
+    Css,
+    /// `"\uD83D\uDD25"` (UTF-16 surrogate pairs)
+    Json,
+    /// `"\xF0\x9F\x94\xA5"` (UTF-8 bytes, valid in any C standard)
+    C,
+}
+
+/// Escape every character of an emoji as a source code literal
+///
+/// Each codepoint of a ZWJ sequence, variation selector or skin tone modifier is
+/// escaped individually, so the literal produces exactly the same string.
+pub fn escape(text: &str, format: EscapeFormat) -> String {
+    match format {
+        EscapeFormat::Rust => quoted(text.chars().map(|c| format!("\\u{{{:X}}}", c as u32))),
+        EscapeFormat::Js | EscapeFormat::Java | EscapeFormat::Json => {
+            quoted(text.encode_utf16().map(|unit| format!("\\u{:04X}", unit)))
+        }
+        EscapeFormat::Python => quoted(text.chars().map(|c| match c as u32 {
+            code @ 0..=0xFFFF => format!("\\u{:04X}", code),
+            code => format!("\\U{:08X}", code),
+        })),
+        EscapeFormat::Html => text
+            .chars()
+            .map(|c| format!("&#x{:X};", c as u32))
+            .collect(),
+        EscapeFormat::Css => quoted(text.chars().map(|c| format!("\\{:06X}", c as u32))),
+        EscapeFormat::C => quoted(text.bytes().map(|byte| format!("\\x{:02X}", byte))),
+    }
+}
+
+/// Join escapes into a double quoted string literal
+fn quoted(escapes: impl Iterator<Item = String>) -> String {
+    format!("\"{}\"", escapes.collect::<String>())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_codepoint() {
+        let cases = [
+            (EscapeFormat::Rust, r#""\u{1F525}""#),
+            (EscapeFormat::Js, r#""\uD83D\uDD25""#),
+            (EscapeFormat::Python, r#""\U0001F525""#),
+            (EscapeFormat::Java, r#""\uD83D\uDD25""#),
+            (EscapeFormat::Html, "&#x1F525;"),
+            (EscapeFormat::Css, r#""\01F525""#),
+            (EscapeFormat::Json, r#""\uD83D\uDD25""#),
+            (EscapeFormat::C, r#""\xF0\x9F\x94\xA5""#),
+        ];
+        for (format, expected) in cases {
+            assert_eq!(escape("🔥", format), expected, "{:?}", format);
+        }
+    }
+
+    #[test]
+    fn test_sequences() {
+        // Skin tone modifier and ZWJ, as produced by apply_skin_tone
+        let technologist = "🧑🏽‍💻";
+        assert_eq!(
+            escape(technologist, EscapeFormat::Rust),
+            r#""\u{1F9D1}\u{1F3FD}\u{200D}\u{1F4BB}""#
+        );
+        assert_eq!(
+            escape(technologist, EscapeFormat::Python),
+            r#""\U0001F9D1\U0001F3FD\u200D\U0001F4BB""#
+        );
+        assert_eq!(escape("❤️", EscapeFormat::Html), "&#x2764;&#xFE0F;");
+    }
+
+    #[test]
+    fn test_escapes_round_trip_as_json() {
+        let heart = "❤️‍🔥";
+        let escaped = escape(heart, EscapeFormat::Json);
+        assert_eq!(serde_json::from_str::<String>(&escaped).unwrap(), heart);
+    }
+}
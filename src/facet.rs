@@ -0,0 +1,170 @@
+use crate::index::{normalize_relaxed, INDEX};
+use crate::model::EmojiMatch;
+use crate::variants::split_sequence;
+use emojis::{Emoji, Group, UnicodeVersion};
+
+/// Filters restricting results to part of the Unicode emoji database
+///
+/// A slang combo passes only if every emoji in it does.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Facets {
+    /// Only emojis in this group
+    pub group: Option<Group>,
+    /// Only emojis in this subgroup, as returned by [`parse_subgroup`]
+    pub subgroup: Option<&'static str>,
+    /// Only emojis introduced in this Unicode version or earlier
+    pub max_unicode: Option<UnicodeVersion>,
+}
+
+impl Facets {
+    /// Whether no filter is set
+    pub fn is_empty(&self) -> bool {
+        *self == Facets::default()
+    }
+
+    /// Whether the emoji passes every filter
+    pub fn allows(&self, emoji: &Emoji) -> bool {
+        self.group.is_none_or(|group| emoji.group() == group)
+            && self
+                .max_unicode
+                .is_none_or(|max| emoji.unicode_version() <= max)
+            && self
+                .subgroup
+                .is_none_or(|subgroup| subgroup_of(emoji) == Some(subgroup))
+    }
+
+    /// Whether every emoji of the match passes every filter
+    pub(crate) fn allows_match(&self, matched: &EmojiMatch) -> bool {
+        if self.is_empty() {
+            return true;
+        }
+
+        match matched {
+            EmojiMatch::Unicode(emoji) => self.allows(emoji),
+            // Symbols that are not emojis (e.g. "λ") have no group or subgroup
+            EmojiMatch::Sequence(sequence) => {
+                split_sequence(sequence).into_iter().all(|part| match part {
+                    Ok(emoji) => self.allows(emoji),
+                    Err(_) => self.group.is_none() && self.subgroup.is_none(),
+                })
+            }
+            EmojiMatch::Combined(parts) => parts.iter().all(|part| self.allows_match(part)),
+        }
+    }
+}
+
+/// The kebab-case name of a group, e.g. "food-and-drink"
+pub fn group_name(group: Group) -> &'static str {
+    INDEX
+        .groups
+        .iter()
+        .find(|(_, g)| *g == group)
+        .map(|(name, _)| *name)
+        .expect("every group is indexed")
+}
+
+/// The Unicode subgroup of an emoji, e.g. "animal-mammal"
+pub fn subgroup_of(emoji: &Emoji) -> Option<&'static str> {
+    INDEX.entry(emoji).map(|entry| entry.subgroup)
+}
+
+/// Compare names ignoring case, separators and "&" spelled out or not
+fn facet_key(name: &str) -> String {
+    normalize_relaxed(&name.replace('&', "and"))
+}
+
+/// Parse a group name such as "food-and-drink" or "Food & Drink"
+pub fn parse_group(name: &str) -> Result<Group, String> {
+    let key = facet_key(name);
+    INDEX
+        .groups
+        .iter()
+        .find(|(group, _)| facet_key(group) == key)
+        .map(|(_, group)| *group)
+        .ok_or_else(|| {
+            let names: Vec<&str> = INDEX.groups.iter().map(|(group, _)| *group).collect();
+            format!(
+                "unknown group '{}' (expected one of: {})",
+                name,
+                names.join(", ")
+            )
+        })
+}
+
+/// Parse a subgroup name such as "animal-mammal"
+pub fn parse_subgroup(name: &str) -> Result<&'static str, String> {
+    let key = facet_key(name);
+    INDEX
+        .subgroups
+        .iter()
+        .find(|(subgroup, _)| facet_key(subgroup) == key)
+        .map(|(subgroup, _)| *subgroup)
+        .ok_or_else(|| {
+            let names: Vec<&str> = INDEX
+                .subgroups
+                .iter()
+                .map(|(subgroup, _)| *subgroup)
+                .collect();
+            format!(
+                "unknown subgroup '{}' (expected one of: {})",
+                name,
+                names.join(", ")
+            )
+        })
+}
+
+/// Parse a Unicode version such as "14.0" or "15"
+pub fn parse_unicode_version(version: &str) -> Result<UnicodeVersion, String> {
+    let (major, minor) = version
+        .trim()
+        .split_once('.')
+        .unwrap_or((version.trim(), "0"));
+    match (major.parse(), minor.parse()) {
+        (Ok(major), Ok(minor)) => Ok(UnicodeVersion::new(major, minor)),
+        _ => Err(format!(
+            "invalid Unicode version '{}' (expected e.g. 14.0)",
+            version
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_facets() {
+        assert_eq!(parse_group("food-and-drink"), Ok(Group::FoodAndDrink));
+        assert_eq!(parse_group("Food & Drink"), Ok(Group::FoodAndDrink));
+        assert!(parse_group("food").is_err());
+        assert_eq!(parse_subgroup("animal-mammal"), Ok("animal-mammal"));
+        assert_eq!(parse_subgroup("Sky & Weather"), Ok("sky-and-weather"));
+        assert!(parse_subgroup("mammal").is_err());
+        assert_eq!(
+            parse_unicode_version("14.0"),
+            Ok(UnicodeVersion::new(14, 0))
+        );
+        assert_eq!(parse_unicode_version("15"), Ok(UnicodeVersion::new(15, 0)));
+        assert!(parse_unicode_version("latest").is_err());
+    }
+
+    #[test]
+    fn test_facets_filter_emojis() {
+        let dog = emojis::get("🐕").unwrap();
+        let melting = emojis::get("🫠").unwrap();
+        let facets = Facets {
+            group: Some(Group::AnimalsAndNature),
+            subgroup: Some("animal-mammal"),
+            max_unicode: Some(UnicodeVersion::new(13, 0)),
+        };
+        assert!(facets.allows(dog));
+        assert!(!facets.allows(melting));
+
+        let old = Facets {
+            max_unicode: Some(UnicodeVersion::new(13, 0)),
+            ..Default::default()
+        };
+        assert!(!old.allows(melting));
+        assert!(old.allows_match(&EmojiMatch::Sequence("💅👑")));
+    }
+}
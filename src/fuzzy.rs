@@ -0,0 +1,113 @@
+/// Maximum number of edits tolerated for a query of the given length in characters
+///
+/// Short queries get no tolerance, since nearly every short word is one edit from another.
+fn max_edits(len: usize) -> usize {
+    match len {
+        0..=2 => 0,
+        3..=5 => 1,
+        _ => 2,
+    }
+}
+
+/// Bounded edit distance matcher for a single query
+///
+/// Uses the optimal string alignment distance: insertions, deletions, substitutions and
+/// transpositions of adjacent characters each count as one edit. The row buffers are
+/// reused across candidates so matching the whole index does not allocate.
+pub(crate) struct FuzzyMatcher {
+    query: Vec<char>,
+    max: usize,
+    rows: [Vec<usize>; 3],
+}
+
+impl FuzzyMatcher {
+    /// Create a matcher, or `None` if the query is too short to tolerate typos
+    pub fn new(query: &str) -> Option<Self> {
+        let query: Vec<char> = query.chars().collect();
+        let max = max_edits(query.len());
+        if max == 0 {
+            return None;
+        }
+
+        let rows = [
+            vec![0; query.len() + 1],
+            vec![0; query.len() + 1],
+            vec![0; query.len() + 1],
+        ];
+        Some(Self { query, max, rows })
+    }
+
+    /// Edit distance between the query and the candidate, if within the tolerance
+    pub fn distance(&mut self, candidate: &str) -> Option<usize> {
+        let n = self.query.len();
+        if candidate.chars().count().abs_diff(n) > self.max {
+            return None;
+        }
+
+        let [two_back, prev, cur] = &mut self.rows;
+        for (j, cell) in prev.iter_mut().enumerate() {
+            *cell = j;
+        }
+
+        let mut prev_char = None;
+        for (i, c) in candidate.chars().enumerate() {
+            cur[0] = i + 1;
+            let mut row_min = cur[0];
+
+            for j in 1..=n {
+                let cost = usize::from(c != self.query[j - 1]);
+                let mut d = (prev[j] + 1).min(cur[j - 1] + 1).min(prev[j - 1] + cost);
+                // Adjacent transposition, e.g. "hevaen" for "heaven"
+                if j > 1 && prev_char == Some(self.query[j - 1]) && c == self.query[j - 2] {
+                    d = d.min(two_back[j - 2] + 1);
+                }
+                cur[j] = d;
+                row_min = row_min.min(d);
+            }
+
+            // Distances never shrink further down, so stop once every cell is too far
+            if row_min > self.max {
+                return None;
+            }
+
+            std::mem::swap(two_back, prev);
+            std::mem::swap(prev, cur);
+            prev_char = Some(c);
+        }
+
+        let distance = prev[n];
+        (distance <= self.max).then_some(distance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn distance(query: &str, candidate: &str) -> Option<usize> {
+        FuzzyMatcher::new(query).unwrap().distance(candidate)
+    }
+
+    #[test]
+    fn test_single_edits() {
+        assert_eq!(distance("firr", "fire"), Some(1));
+        assert_eq!(distance("hert", "heart"), Some(1));
+        assert_eq!(distance("hearts", "heart"), Some(1));
+        assert_eq!(distance("heart", "heart"), Some(0));
+    }
+
+    #[test]
+    fn test_transposition_is_one_edit() {
+        assert_eq!(distance("haert", "heart"), Some(1));
+        assert_eq!(distance("thubmsup", "thumbsup"), Some(1));
+    }
+
+    #[test]
+    fn test_tolerance_depends_on_query_length() {
+        assert!(FuzzyMatcher::new("ab").is_none());
+        assert_eq!(distance("firr", "fear"), None);
+        assert_eq!(distance("rainbwo", "rainbow"), Some(1));
+        assert_eq!(distance("ranibwo", "rainbow"), Some(2));
+        assert_eq!(distance("rnaibwo", "rainbow"), None);
+    }
+}
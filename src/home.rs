@@ -0,0 +1,180 @@
+use crate::model::{Comparison, EmojiMatch, Explanation, MatchSource, MatchStage, SearchResult};
+use crate::rank::score;
+use crate::search::Searcher;
+use emojis::{Emoji, SkinTone};
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Emojis used most often worldwide, filling the home screen after favorites and recent picks
+const POPULAR: &[&str] = &[
+    "😂", "❤️", "🤣", "👍", "😭", "🙏", "😘", "🥰", "😍", "😊", "🎉", "😁", "💕", "🥺", "😅", "🔥",
+    "🤦", "🤷", "🙄", "😆", "🤗", "😉", "🎂", "🤔", "👏", "🙂", "😳", "🥳", "😎", "👌",
+];
+
+/// How many recently used emojis the history keeps
+const HISTORY_SIZE: usize = 50;
+
+/// Why an emoji is on the home screen, used as its result term
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HomeSection {
+    /// Pinned by the user
+    Favorite,
+    /// Picked recently
+    Recent,
+    /// Among the most used emojis
+    Popular,
+}
+
+impl HomeSection {
+    /// Stable name used as the result term
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HomeSection::Favorite => "favorite",
+            HomeSection::Recent => "recent",
+            HomeSection::Popular => "popular",
+        }
+    }
+}
+
+/// The emojis shown for an empty query: favorites, then recent picks, then popular emojis
+///
+/// Emojis are listed once, with the skin tone they were given or else the searcher's, and
+/// only if they pass the searcher's facets. Text that is not an emoji is skipped.
+pub fn home(searcher: &Searcher, favorites: &[String], recent: &[String]) -> Vec<SearchResult> {
+    let options = searcher.options();
+    let popular: Vec<String> = POPULAR.iter().map(|emoji| emoji.to_string()).collect();
+    let sections = [
+        (HomeSection::Favorite, favorites),
+        (HomeSection::Recent, recent),
+        (HomeSection::Popular, popular.as_slice()),
+    ];
+
+    let mut seen = HashSet::new();
+    let mut results = Vec::new();
+    for (section, emojis) in sections {
+        for text in emojis {
+            let Some(emoji) = emojis::get(text.trim()) else {
+                continue;
+            };
+            let base = emoji.with_skin_tone(SkinTone::Default).unwrap_or(emoji);
+            if !options.facets.allows(base) || !seen.insert(base.as_str()) {
+                continue;
+            }
+            results.push(home_result(searcher, section, emoji, base));
+        }
+    }
+
+    results.truncate(options.limit);
+    results
+}
+
+/// A home screen result, keeping an explicit skin tone or gender
+fn home_result(
+    searcher: &Searcher,
+    section: HomeSection,
+    emoji: &'static Emoji,
+    base: &'static Emoji,
+) -> SearchResult {
+    let matched = EmojiMatch::Unicode(base);
+    SearchResult {
+        emoji: if emoji == base {
+            searcher.render(&matched)
+        } else {
+            emoji.as_str().to_string()
+        },
+        term: section.as_str().to_string(),
+        stage: MatchStage::Browse,
+        source: MatchSource::Name,
+        score: score(MatchStage::Browse, "", "", 0),
+        explanation: Explanation {
+            field: base.name(),
+            query: String::new(),
+            comparison: Comparison::Any,
+        },
+        matched,
+    }
+}
+
+/// Recently used emojis stored one per line, most recent first
+///
+/// A missing history file is an empty history.
+pub fn load_history(path: &Path) -> io::Result<Vec<String>> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.trim().to_string())
+            .collect()),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(err),
+    }
+}
+
+/// Move an emoji to the top of the history, keeping the most recent entries
+pub fn record_use(path: &Path, emoji: &str) -> io::Result<()> {
+    let emoji = emoji.trim();
+    let mut history = load_history(path)?;
+    history.retain(|used| used != emoji);
+    history.insert(0, emoji.to_string());
+    history.truncate(HISTORY_SIZE);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, history.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::facet::Facets;
+    use crate::search::SearchOptions;
+    use crate::variants::SkinTone as Tone;
+
+    fn emojis(results: &[SearchResult]) -> Vec<&str> {
+        results.iter().map(|r| r.emoji.as_str()).collect()
+    }
+
+    #[test]
+    fn test_home_sections() {
+        let favorites = vec!["🦀".to_string(), "not an emoji".to_string()];
+        let recent = vec!["👍🏽".to_string(), "🦀".to_string()];
+        let results = home(&Searcher::default(), &favorites, &recent);
+
+        assert_eq!(emojis(&results)[..4], ["🦀", "👍🏽", "😂", "❤️"]);
+        assert_eq!(results[0].term, "favorite");
+        assert_eq!(results[1].term, "recent");
+        assert_eq!(results[2].term, "popular");
+        // 👍 was already listed with a skin tone
+        assert!(!emojis(&results).contains(&"👍"));
+        assert_eq!(results.len(), 10);
+    }
+
+    #[test]
+    fn test_home_options() {
+        let searcher = Searcher::new(SearchOptions {
+            skin_tone: Some(Tone::Dark),
+            facets: Facets {
+                group: Some(emojis::Group::PeopleAndBody),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let results = home(&searcher, &[], &[]);
+        assert_eq!(emojis(&results)[..2], ["👍🏿", "🙏🏿"]);
+    }
+
+    #[test]
+    fn test_record_use() {
+        let path = std::env::temp_dir()
+            .join(format!("almoji-history-{}", std::process::id()))
+            .join("history");
+        record_use(&path, "🔥").unwrap();
+        record_use(&path, "🦀 ").unwrap();
+        record_use(&path, "🔥").unwrap();
+        assert_eq!(load_history(&path).unwrap(), vec!["🔥", "🦀"]);
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+}
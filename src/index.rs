@@ -0,0 +1,252 @@
+use crate::model::EmojiMatch;
+use std::collections::HashSet;
+
+pub(crate) use crate::normalize::normalize_relaxed;
+
+// Emoji entries, the sorted token table, group and subgroup names and the sorted slang
+// terms, generated by build.rs
+include!(concat!(env!("OUT_DIR"), "/emoji_index.rs"));
+include!(concat!(env!("OUT_DIR"), "/slang_index.rs"));
+
+/// Search index over the emoji database and slang table, fully built at compile time
+pub(crate) static INDEX: SearchIndex = SearchIndex {
+    emojis: EMOJI_ENTRIES,
+    slang: SLANG_ENTRIES,
+    groups: GROUPS,
+    subgroups: SUBGROUPS,
+    tokens: TOKENS,
+};
+
+/// A shortcode with its precomputed normalized forms
+pub(crate) struct IndexedShortcode {
+    /// Shortcode without colons, as written in the database
+    pub raw: &'static str,
+    /// Lowercased shortcode
+    pub lower: &'static str,
+    /// Shortcode normalized for relaxed matching
+    pub relaxed: &'static str,
+}
+
+/// A Unicode emoji with its precomputed normalized names and shortcodes
+pub(crate) struct EmojiEntry {
+    /// The emoji as a string, as stored in the database
+    pub text: &'static str,
+    /// Lowercased CLDR name
+    pub name: &'static str,
+    /// Name normalized for relaxed matching
+    pub name_relaxed: &'static str,
+    /// Unicode subgroup, e.g. "animal-mammal"
+    pub subgroup: &'static str,
+    pub shortcodes: &'static [IndexedShortcode],
+}
+
+impl EmojiEntry {
+    /// The database emoji this entry was generated from
+    pub fn emoji(&self) -> &'static emojis::Emoji {
+        emojis::get(self.text).expect("index is generated from the same emoji database")
+    }
+}
+
+/// A slang term with its precomputed normalized form
+pub(crate) struct SlangEntry {
+    pub term: &'static str,
+    /// Term normalized for relaxed matching
+    pub relaxed: &'static str,
+    /// The raw mapping values from the slang table
+    pub values: &'static [&'static str],
+}
+
+impl SlangEntry {
+    /// The mapping values resolved to database emojis or sequences
+    pub fn emojis(&self) -> impl Iterator<Item = (&'static str, EmojiMatch)> {
+        self.values
+            .iter()
+            .map(|value| (*value, resolve_slang_emoji(value)))
+    }
+}
+
+/// Which part of an emoji a token was taken from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenKind {
+    Name,
+    Word,
+    Shortcode,
+}
+
+/// Precomputed lookup tables over the emoji database and slang table
+pub(crate) struct SearchIndex {
+    /// All default-tone emojis in Unicode CLDR order
+    pub emojis: &'static [EmojiEntry],
+    /// All slang terms sorted alphabetically
+    pub slang: &'static [SlangEntry],
+    /// Kebab-case group names with their group, in CLDR order
+    pub groups: &'static [(&'static str, emojis::Group)],
+    /// Subgroup names with the name of their group, in CLDR order
+    pub subgroups: &'static [(&'static str, &'static str)],
+    /// Name, name word and shortcode tokens with their emoji entry id, sorted
+    tokens: &'static [(&'static str, usize, TokenKind)],
+}
+
+impl SearchIndex {
+    /// Look up the entry of an emoji, ignoring any skin tone
+    pub fn entry(&self, emoji: &emojis::Emoji) -> Option<&'static EmojiEntry> {
+        let base = emoji
+            .with_skin_tone(emojis::SkinTone::Default)
+            .unwrap_or(emoji);
+        EMOJI_IDS.get(base.as_str()).map(|&id| &EMOJI_ENTRIES[id])
+    }
+
+    /// Tokens starting with the prefix, in sorted order
+    fn tokens_with_prefix<'a>(
+        &self,
+        prefix: &'a str,
+    ) -> impl Iterator<Item = &'static (&'static str, usize, TokenKind)> + 'a {
+        let start = self.tokens.partition_point(|(token, _, _)| *token < prefix);
+        self.tokens[start..]
+            .iter()
+            .take_while(move |(token, _, _)| token.starts_with(prefix))
+    }
+
+    /// Look up a slang term exactly
+    pub fn slang_exact(&self, term: &str) -> Option<&SlangEntry> {
+        self.slang
+            .binary_search_by(|entry| entry.term.cmp(term))
+            .ok()
+            .map(|i| &self.slang[i])
+    }
+
+    /// Slang terms starting with the prefix, in alphabetical order
+    pub fn slang_prefix<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a SlangEntry> {
+        let start = self.slang.partition_point(|entry| entry.term < prefix);
+        self.slang[start..]
+            .iter()
+            .take_while(move |entry| entry.term.starts_with(prefix))
+    }
+
+    /// Emojis whose lowercased name or a shortcode equals the query, in CLDR order
+    pub fn emoji_exact(&self, query: &str) -> impl Iterator<Item = &'static EmojiEntry> {
+        // Equal tokens are adjacent and sorted by entry id
+        let mut ids: Vec<usize> = self
+            .tokens_with_prefix(query)
+            .filter(|(token, _, kind)| *token == query && *kind != TokenKind::Word)
+            .map(|(_, id, _)| *id)
+            .collect();
+        ids.dedup();
+        let emojis = self.emojis;
+        ids.into_iter().map(move |id| &emojis[id])
+    }
+
+    /// Emojis with a name, name word or shortcode starting with the prefix, in CLDR order
+    pub fn emoji_prefix(&self, prefix: &str) -> impl Iterator<Item = &'static EmojiEntry> {
+        let mut ids: Vec<usize> = self
+            .tokens_with_prefix(prefix)
+            .map(|(_, id, _)| *id)
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        let emojis = self.emojis;
+        ids.into_iter().map(move |id| &emojis[id])
+    }
+
+    /// Emojis matched by every word, in CLDR order
+    ///
+    /// A word matches an emoji when it starts one of its name words or shortcodes, or a
+    /// slang term mapping to it, so word order does not matter.
+    pub fn emoji_all_words(&self, words: &[&str]) -> impl Iterator<Item = &'static EmojiEntry> {
+        let mut matched: Option<HashSet<&'static str>> = None;
+
+        for word in words {
+            let mut texts: HashSet<&'static str> =
+                self.emoji_prefix(word).map(|entry| entry.text).collect();
+            for entry in self.slang_prefix(word) {
+                texts.extend(
+                    entry
+                        .emojis()
+                        .filter_map(|(_, e)| e.unicode())
+                        .map(|e| e.as_str()),
+                );
+            }
+
+            matched = Some(match matched {
+                Some(previous) => previous.intersection(&texts).copied().collect(),
+                None => texts,
+            });
+        }
+
+        let matched = matched.unwrap_or_default();
+        self.emojis
+            .iter()
+            .filter(move |entry| matched.contains(entry.text))
+    }
+}
+
+/// Resolve a slang mapping value to a database emoji or a multi-emoji sequence
+fn resolve_slang_emoji(emoji_str: &'static str) -> EmojiMatch {
+    match emojis::get(emoji_str) {
+        Some(emoji) => EmojiMatch::Unicode(emoji),
+        // If not found in database, it is a group of emojis (e.g., "💗💜💙")
+        None => EmojiMatch::Sequence(emoji_str),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slang_entries_are_sorted() {
+        assert!(SLANG_ENTRIES
+            .windows(2)
+            .all(|pair| pair[0].term < pair[1].term));
+        assert_eq!(SLANG_ENTRIES.len(), crate::SLANG_MAP.len());
+    }
+
+    #[test]
+    fn test_tokens_are_sorted() {
+        assert!(TOKENS.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+    }
+
+    #[test]
+    fn test_emoji_prefix_in_cldr_order() {
+        let ids: Vec<usize> = INDEX
+            .emoji_prefix("cat")
+            .map(|entry| {
+                INDEX
+                    .emojis
+                    .iter()
+                    .position(|e| e.text == entry.text)
+                    .unwrap()
+            })
+            .collect();
+        assert!(!ids.is_empty());
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_all_words_ignores_order() {
+        let texts = |words: &[&str]| -> Vec<&str> {
+            INDEX
+                .emoji_all_words(words)
+                .map(|entry| entry.text)
+                .collect()
+        };
+        assert!(texts(&["cold", "face"]).contains(&"🥶"));
+        assert_eq!(texts(&["cold", "face"]), texts(&["face", "cold"]));
+    }
+
+    #[test]
+    fn test_subgroups_assigned() {
+        let entry = |text| INDEX.entry(emojis::get(text).unwrap()).unwrap();
+        assert_eq!(entry("😀").subgroup, "face-smiling");
+        assert_eq!(entry("🐕").subgroup, "animal-mammal");
+        assert_eq!(entry("🦀").subgroup, "animal-marine");
+        assert_eq!(entry("👍🏽").subgroup, "hand-fingers-closed");
+        assert_eq!(entry("🇦🇺").subgroup, "country-flag");
+    }
+
+    #[test]
+    fn test_slang_entries_resolved() {
+        let entry = INDEX.slang_exact("weed").unwrap();
+        assert!(entry.emojis().all(|(_, e)| e.unicode().is_some()));
+    }
+}
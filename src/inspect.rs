@@ -0,0 +1,146 @@
+use serde::Serialize;
+
+/// The codepoints and encodings of an emoji, for developers
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Inspection {
+    /// Space separated codepoints, e.g. "U+1F9D1 U+200D U+1F4BB"
+    pub sequence: String,
+    pub codepoints: Vec<Codepoint>,
+    /// UTF-8 bytes as space separated hex, e.g. "F0 9F 94 A5"
+    pub utf8: String,
+    /// UTF-16 code units as space separated hex, e.g. "D83D DD25"
+    pub utf16: String,
+}
+
+/// A single codepoint of an emoji and what it does there
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Codepoint {
+    /// The codepoint in `U+` notation, e.g. "U+200D"
+    pub codepoint: String,
+    /// The emoji name, or the Unicode name for joiners, selectors and tags
+    pub name: Option<String>,
+}
+
+/// Break text down into its codepoints and UTF-8 and UTF-16 encodings
+pub fn inspect(text: &str) -> Inspection {
+    let codepoints: Vec<Codepoint> = text
+        .chars()
+        .map(|c| Codepoint {
+            codepoint: format!("U+{:04X}", c as u32),
+            name: codepoint_name(c),
+        })
+        .collect();
+
+    Inspection {
+        sequence: join(codepoints.iter().map(|c| c.codepoint.clone())),
+        codepoints,
+        utf8: join(text.bytes().map(|byte| format!("{:02X}", byte))),
+        utf16: join(text.encode_utf16().map(|unit| format!("{:04X}", unit))),
+    }
+}
+
+/// Name a codepoint, preferring the emoji database (e.g. "laptop")
+fn codepoint_name(c: char) -> Option<String> {
+    let mut buffer = [0; 4];
+    if let Some(emoji) = emojis::get(c.encode_utf8(&mut buffer)) {
+        return Some(emoji.name().to_string());
+    }
+
+    let name = match c {
+        '\u{200D}' => "zero width joiner".to_string(),
+        '\u{FE0E}' => "variation selector-15 (text style)".to_string(),
+        '\u{FE0F}' => "variation selector-16 (emoji style)".to_string(),
+        '\u{20E3}' => "combining enclosing keycap".to_string(),
+        '\u{1F3FB}' => "emoji modifier: light skin tone".to_string(),
+        '\u{1F3FC}' => "emoji modifier: medium-light skin tone".to_string(),
+        '\u{1F3FD}' => "emoji modifier: medium skin tone".to_string(),
+        '\u{1F3FE}' => "emoji modifier: medium-dark skin tone".to_string(),
+        '\u{1F3FF}' => "emoji modifier: dark skin tone".to_string(),
+        '\u{E007F}' => "cancel tag".to_string(),
+        '#' => "number sign".to_string(),
+        '*' => "asterisk".to_string(),
+        '0'..='9' => format!("digit {}", c),
+        '\u{1F1E6}'..='\u{1F1FF}' => {
+            format!(
+                "regional indicator symbol letter {}",
+                offset_char(c, 0x1F1E6, 'a')
+            )
+        }
+        '\u{E0061}'..='\u{E007A}' => {
+            format!("tag latin small letter {}", offset_char(c, 0xE0061, 'a'))
+        }
+        '\u{E0030}'..='\u{E0039}' => format!("tag digit {}", offset_char(c, 0xE0030, '0')),
+        _ => return None,
+    };
+    Some(name)
+}
+
+/// The character at the same offset from `base` as `c` is from `start`
+fn offset_char(c: char, start: u32, base: char) -> char {
+    char::from_u32(base as u32 + (c as u32 - start)).unwrap_or(base)
+}
+
+fn join(parts: impl Iterator<Item = String>) -> String {
+    parts.collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(inspection: &Inspection) -> Vec<&str> {
+        inspection
+            .codepoints
+            .iter()
+            .map(|c| c.name.as_deref().unwrap_or("?"))
+            .collect()
+    }
+
+    #[test]
+    fn test_inspect_zwj_sequence() {
+        let technologist = inspect("🧑🏽‍💻");
+        assert_eq!(technologist.sequence, "U+1F9D1 U+1F3FD U+200D U+1F4BB");
+        assert_eq!(
+            names(&technologist),
+            vec![
+                "person",
+                "emoji modifier: medium skin tone",
+                "zero width joiner",
+                "laptop"
+            ]
+        );
+        assert_eq!(technologist.utf16, "D83E DDD1 D83C DFFD 200D D83D DCBB");
+    }
+
+    #[test]
+    fn test_inspect_encodings() {
+        let fire = inspect("🔥");
+        assert_eq!(fire.utf8, "F0 9F 94 A5");
+        assert_eq!(fire.utf16, "D83D DD25");
+
+        let keycap = inspect("#️⃣");
+        assert_eq!(
+            names(&keycap),
+            vec![
+                "number sign",
+                "variation selector-16 (emoji style)",
+                "combining enclosing keycap"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inspect_flags() {
+        assert_eq!(
+            names(&inspect("🇯🇵")),
+            vec![
+                "regional indicator symbol letter j",
+                "regional indicator symbol letter p"
+            ]
+        );
+        let scotland = inspect("🏴󠁧󠁢󠁳󠁣󠁴󠁿");
+        let scotland = names(&scotland);
+        assert_eq!(scotland[1], "tag latin small letter g");
+        assert_eq!(scotland.last(), Some(&"cancel tag"));
+    }
+}
@@ -0,0 +1,45 @@
+//! A blazingly fast emoji search library
+//!
+//! This crate powers the `almoji` CLI and exposes the same search and slang
+//! ranking so other Rust tools can embed it in-process.
+
+pub mod alfred;
+mod demojify;
+mod describe;
+mod emojify;
+mod escape;
+mod facet;
+mod fuzzy;
+mod home;
+mod index;
+mod inspect;
+mod listing;
+mod model;
+mod normalize;
+pub mod output;
+mod query;
+mod rank;
+pub mod raycast;
+pub mod rofi;
+mod search;
+mod slang;
+pub mod ulauncher;
+mod variants;
+
+pub use demojify::{demojify, DemojifyStyle};
+pub use describe::{describe, preview, Combo, Description, Preview, SlangUse};
+pub use emojify::{emojify, EmojifyOptions};
+pub use escape::{escape, EscapeFormat};
+pub use facet::{
+    group_name, parse_group, parse_subgroup, parse_unicode_version, subgroup_of, Facets,
+};
+pub use home::{home, load_history, record_use, HomeSection};
+pub use inspect::{inspect, Codepoint, Inspection};
+pub use listing::{fzf_emoji, list_all, list_results, Listing};
+pub use model::{
+    Comparison, EmojiMatch, Explanation, Match, MatchSource, MatchStage, SearchResult,
+};
+pub use query::{Query, QueryError};
+pub use search::{find_emojis, search_emojis, SearchOptions, Searcher};
+pub use slang::{Slang, SLANG_MAP};
+pub use variants::{apply_gender, apply_skin_tone, Gender, SkinTone};
@@ -0,0 +1,164 @@
+use crate::index::INDEX;
+use crate::model::{EmojiMatch, SearchResult};
+use crate::output::match_name;
+use crate::search::Searcher;
+use std::collections::HashMap;
+
+/// An emoji or slang combo with every word that finds it, for launchers that filter themselves
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Listing {
+    /// The emoji with the searcher's skin tone and gender applied
+    pub emoji: String,
+    /// The CLDR name, or the names of every emoji in a combo joined by " + "
+    pub name: String,
+    /// Shortcodes without colons, empty for combos
+    pub shortcodes: Vec<&'static str>,
+    /// Slang terms mapping to exactly this emoji or combo
+    pub slang: Vec<&'static str>,
+    /// The underlying emoji or emoji sequence
+    pub matched: EmojiMatch,
+}
+
+impl Listing {
+    /// Shortcodes with colons followed by slang terms, space separated
+    pub fn keywords(&self) -> String {
+        let shortcodes = self.shortcodes.iter().map(|code| format!(":{}:", code));
+        let slang = self.slang.iter().map(|term| term.to_string());
+        shortcodes.chain(slang).collect::<Vec<_>>().join(" ")
+    }
+
+    /// A tab separated line for fzf: emoji, name and keywords
+    pub fn fzf_line(&self) -> String {
+        format!("{}\t{}\t{}", self.emoji, self.name, self.keywords())
+    }
+}
+
+/// The emoji of an fzf line, its first tab separated field
+pub fn fzf_emoji(line: &str) -> &str {
+    line.split('\t').next().unwrap_or_default().trim()
+}
+
+/// Slang terms by the emoji or combo they map to, with combos in first seen order
+struct SlangTerms {
+    terms: HashMap<&'static str, Vec<&'static str>>,
+    combos: Vec<&'static str>,
+}
+
+impl SlangTerms {
+    fn new() -> Self {
+        let mut terms: HashMap<&'static str, Vec<&'static str>> = HashMap::new();
+        let mut combos = Vec::new();
+
+        for entry in INDEX.slang {
+            for (value, matched) in entry.emojis() {
+                let key = match matched {
+                    EmojiMatch::Unicode(emoji) => emoji.as_str(),
+                    _ => value,
+                };
+                let terms = terms.entry(key).or_default();
+                if terms.is_empty() && matches!(matched, EmojiMatch::Sequence(_)) {
+                    combos.push(value);
+                }
+                if !terms.contains(&entry.term) {
+                    terms.push(entry.term);
+                }
+            }
+        }
+
+        SlangTerms { terms, combos }
+    }
+
+    fn of(&self, matched: &EmojiMatch) -> Vec<&'static str> {
+        self.terms
+            .get(matched.base().as_str())
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// Build the listing entry for a match
+fn listing(searcher: &Searcher, matched: EmojiMatch, slang: &SlangTerms) -> Listing {
+    Listing {
+        emoji: searcher.render(&matched),
+        name: match_name(&matched),
+        shortcodes: matched
+            .unicode()
+            .map(|emoji| emoji.shortcodes().collect())
+            .unwrap_or_default(),
+        slang: slang.of(&matched),
+        matched,
+    }
+}
+
+/// Every emoji in CLDR order followed by every slang combo, within the searcher's facets
+pub fn list_all(searcher: &Searcher) -> Vec<Listing> {
+    let slang = SlangTerms::new();
+    let facets = searcher.options().facets;
+
+    let emojis = INDEX
+        .emojis
+        .iter()
+        .map(|entry| EmojiMatch::Unicode(entry.emoji()));
+    let combos = slang.combos.iter().map(|combo| EmojiMatch::Sequence(combo));
+
+    emojis
+        .chain(combos)
+        .filter(|matched| facets.allows_match(matched))
+        .map(|matched| listing(searcher, matched, &slang))
+        .collect()
+}
+
+/// The listing entries of search results, in the same order
+pub fn list_results(searcher: &Searcher, results: &[SearchResult]) -> Vec<Listing> {
+    let slang = SlangTerms::new();
+    results
+        .iter()
+        .map(|result| Listing {
+            emoji: result.emoji.clone(),
+            ..listing(searcher, result.matched.clone(), &slang)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::facet::Facets;
+    use crate::search::SearchOptions;
+    use crate::variants::SkinTone;
+
+    #[test]
+    fn test_list_all() {
+        let listings = list_all(&Searcher::default());
+        assert_eq!(
+            listings.len(),
+            INDEX.emojis.len() + SlangTerms::new().combos.len()
+        );
+
+        let fire = listings.iter().find(|l| l.emoji == "🔥").unwrap();
+        assert_eq!(fire.shortcodes, vec!["fire"]);
+        assert!(fire.slang.contains(&"lit"));
+        assert!(fire.keywords().starts_with(":fire: "));
+        assert!(fire.fzf_line().starts_with("🔥\tfire\t:fire: "));
+        assert_eq!(fzf_emoji(&fire.fzf_line()), "🔥");
+
+        let combo = listings.iter().find(|l| l.emoji == "💅👑").unwrap();
+        assert_eq!(combo.name, "nail polish + crown");
+        assert!(combo.slang.contains(&"cunt"));
+    }
+
+    #[test]
+    fn test_list_all_options() {
+        let searcher = Searcher::new(SearchOptions {
+            skin_tone: Some(SkinTone::Dark),
+            facets: Facets {
+                subgroup: Some("hand-fingers-open"),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let listings = list_all(&searcher);
+        assert!(listings.iter().any(|l| l.emoji == "👋🏿"));
+        assert!(!listings.iter().any(|l| l.emoji == "🔥"));
+    }
+}
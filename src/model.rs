@@ -0,0 +1,193 @@
+use crate::inspect::{inspect, Inspection};
+use serde::Serialize;
+
+/// The stage of the search that produced a match, in priority order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchStage {
+    /// Query equals a slang term
+    ExactSlang,
+    /// Query equals an emoji name or shortcode
+    Exact,
+    /// A slang term starts with the query
+    PrefixSlang,
+    /// An emoji name, name word or shortcode starts with the query
+    Prefix,
+    /// Every word of a multi-word query starts a name word, shortcode or slang term
+    AllWords,
+    /// A slang term contains the query (relaxed)
+    SubstringSlang,
+    /// An emoji name or shortcode contains the query (relaxed)
+    Substring,
+    /// A slang term, emoji name, name word or shortcode is within a few typos of the query
+    Fuzzy,
+    /// An empty query listing every emoji in the selected facets
+    Browse,
+}
+
+/// Where the matched term came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MatchSource {
+    /// A term from the custom slang table
+    Slang,
+    /// The CLDR name of the emoji
+    Name,
+    /// A GitHub/Slack style shortcode
+    Shortcode,
+}
+
+impl MatchStage {
+    /// Stable name used in explain output
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MatchStage::ExactSlang => "exact_slang",
+            MatchStage::Exact => "exact",
+            MatchStage::PrefixSlang => "prefix_slang",
+            MatchStage::Prefix => "prefix",
+            MatchStage::AllWords => "all_words",
+            MatchStage::SubstringSlang => "substring_slang",
+            MatchStage::Substring => "substring",
+            MatchStage::Fuzzy => "fuzzy",
+            MatchStage::Browse => "browse",
+        }
+    }
+}
+
+impl MatchSource {
+    /// Stable name used in explain output
+    pub fn as_str(self) -> &'static str {
+        match self {
+            MatchSource::Slang => "slang",
+            MatchSource::Name => "name",
+            MatchSource::Shortcode => "shortcode",
+        }
+    }
+}
+
+/// How the normalized query was compared with the matched field
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Comparison {
+    /// The field equals the query
+    Equals,
+    /// The field starts with the query
+    Prefix,
+    /// A word of the field starts with the query
+    WordPrefix,
+    /// Each query word starts a name word, shortcode or slang term, in any order
+    AllWords,
+    /// The field contains the query
+    Contains,
+    /// The field contains the query once separators are removed
+    RelaxedContains,
+    /// The query contains the field once separators are removed
+    RelaxedContainedIn,
+    /// The field is within a few edits of the query once separators are removed
+    Fuzzy,
+    /// Any field matches, as the query is empty
+    Any,
+}
+
+impl Comparison {
+    /// Human readable description of the comparison
+    pub fn describe(self) -> &'static str {
+        match self {
+            Comparison::Equals => "equals",
+            Comparison::Prefix => "starts with",
+            Comparison::WordPrefix => "has a word starting with",
+            Comparison::AllWords => "matches every word of",
+            Comparison::Contains => "contains",
+            Comparison::RelaxedContains => "contains (relaxed)",
+            Comparison::RelaxedContainedIn => "is contained in (relaxed)",
+            Comparison::Fuzzy => "is a close spelling of",
+            Comparison::Any => "matches the empty query",
+        }
+    }
+}
+
+/// Why a match was made: the normalized strings compared and how
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Explanation {
+    /// The field value as compared (lowercased, or relaxed for relaxed comparisons)
+    pub field: &'static str,
+    /// The query as compared (lowercased, or relaxed for relaxed comparisons)
+    pub query: String,
+    pub comparison: Comparison,
+}
+
+/// What a match resolved to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EmojiMatch {
+    /// A single emoji from the Unicode database
+    Unicode(&'static emojis::Emoji),
+    /// A slang combo made of several emojis (e.g. "💗💜💙")
+    Sequence(&'static str),
+    /// Several matches joined together by a query ending with `+`
+    Combined(Vec<EmojiMatch>),
+}
+
+impl EmojiMatch {
+    /// The emoji text without any skin tone or gender modifiers
+    pub fn base(&self) -> String {
+        match self {
+            EmojiMatch::Unicode(emoji) => emoji.as_str().to_string(),
+            EmojiMatch::Sequence(sequence) => sequence.to_string(),
+            EmojiMatch::Combined(parts) => parts.iter().map(EmojiMatch::base).collect(),
+        }
+    }
+
+    /// The Unicode emoji, if this match is a single database entry
+    pub fn unicode(&self) -> Option<&'static emojis::Emoji> {
+        match self {
+            EmojiMatch::Unicode(emoji) => Some(emoji),
+            _ => None,
+        }
+    }
+}
+
+/// A raw hit produced by [`search_emojis`](crate::search_emojis)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    /// The emoji or emoji sequence that matched
+    pub emoji: EmojiMatch,
+    /// The term that matched the query (slang term, name or shortcode)
+    pub term: String,
+    /// The search stage that produced this match
+    pub stage: MatchStage,
+    /// Where the matched term came from
+    pub source: MatchSource,
+    /// Ranking score; higher is better
+    pub score: u32,
+    /// Why this match was made
+    pub explanation: Explanation,
+}
+
+/// A single emoji found for a query, ready to be displayed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SearchResult {
+    /// The emoji with any skin tone or gender modifiers applied
+    pub emoji: String,
+    /// The term that matched the query (slang term, name or shortcode)
+    pub term: String,
+    /// The search stage that produced this result
+    ///
+    /// For combined (`+`) results this is the stage of the best match.
+    pub stage: MatchStage,
+    /// Where the matched term came from
+    pub source: MatchSource,
+    /// Ranking score; higher is better
+    pub score: u32,
+    /// Why this result was matched
+    pub explanation: Explanation,
+    /// The underlying emoji or emoji sequence
+    #[serde(skip)]
+    pub matched: EmojiMatch,
+}
+
+impl SearchResult {
+    /// The codepoints and encodings of the emoji as displayed
+    pub fn inspect(&self) -> Inspection {
+        inspect(&self.emoji)
+    }
+}
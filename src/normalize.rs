@@ -0,0 +1,12 @@
+//! String normalization shared by the build script and the search index
+
+/// Normalize strings for relaxed matching (lowercase, remove common separators)
+pub fn normalize_relaxed(s: &str) -> String {
+    let mut normalized = String::with_capacity(s.len());
+    for c in s.chars() {
+        if !matches!(c, ' ' | '_' | '-' | ':') {
+            normalized.extend(c.to_lowercase());
+        }
+    }
+    normalized
+}
@@ -0,0 +1,449 @@
+use crate::escape::{escape, EscapeFormat};
+use crate::facet::{group_name, subgroup_of};
+use crate::inspect::inspect;
+use crate::model::{EmojiMatch, MatchSource, SearchResult};
+use crate::variants::split_sequence;
+use serde::Serialize;
+use std::fmt;
+
+/// How search results are printed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum OutputFormat {
+    /// `🔥 (fire)`, one result per line
+    #[default]
+    Text,
+    /// A JSON array of records
+    Json,
+    /// One JSON record per line
+    Jsonl,
+    /// Comma separated records with a header row
+    Csv,
+    /// Tab separated records with a header row
+    Tsv,
+    /// Just the emoji, one per line
+    EmojiOnly,
+}
+
+/// The stable schema of a result in the structured formats
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OutputRecord {
+    /// The emoji with any skin tone or gender modifiers applied
+    pub emoji: String,
+    /// The term that matched the query
+    pub term: String,
+    /// The CLDR name, or the names of every emoji in a combo joined by " + "
+    pub name: String,
+    /// Shortcodes without colons, empty for combos
+    pub shortcodes: Vec<&'static str>,
+    /// Kebab-case Unicode group, if the result is a single emoji
+    pub group: Option<&'static str>,
+    pub source: MatchSource,
+    pub score: u32,
+}
+
+/// Column names of the CSV and TSV header row, in record order
+const COLUMNS: [&str; 7] = [
+    "emoji",
+    "term",
+    "name",
+    "shortcodes",
+    "group",
+    "source",
+    "score",
+];
+
+impl OutputRecord {
+    /// Flatten a search result into a record
+    pub fn from_result(result: &SearchResult) -> Self {
+        let unicode = result.matched.unicode();
+        OutputRecord {
+            emoji: result.emoji.clone(),
+            term: result.term.clone(),
+            name: match_name(&result.matched),
+            shortcodes: unicode
+                .map(|emoji| emoji.shortcodes().collect())
+                .unwrap_or_default(),
+            group: unicode.map(|emoji| group_name(emoji.group())),
+            source: result.source,
+            score: result.score,
+        }
+    }
+
+    /// The record as column values, in the order of the header row
+    fn columns(&self) -> [String; 7] {
+        [
+            self.emoji.clone(),
+            self.term.clone(),
+            self.name.clone(),
+            self.shortcodes.join(" "),
+            self.group.unwrap_or_default().to_string(),
+            self.source.as_str().to_string(),
+            self.score.to_string(),
+        ]
+    }
+}
+
+/// The name of a match, joining the names of the emojis in a combo
+pub(crate) fn match_name(matched: &EmojiMatch) -> String {
+    match matched {
+        EmojiMatch::Unicode(emoji) => emoji.name().to_string(),
+        _ => split_sequence(&matched.base())
+            .into_iter()
+            .map(|part| match part {
+                Ok(emoji) => emoji.name(),
+                Err(text) => text,
+            })
+            .collect::<Vec<_>>()
+            .join(" + "),
+    }
+}
+
+/// Render results in the format, ending with a newline unless empty
+///
+/// The JSON formats and header rows are printed even when there are no results.
+pub fn format_results(results: &[SearchResult], format: OutputFormat) -> String {
+    let records = results.iter().map(OutputRecord::from_result);
+    let mut output = String::new();
+
+    match format {
+        OutputFormat::Text => {
+            for result in results {
+                output.push_str(&format!("{} ({})\n", result.emoji, result.term));
+            }
+        }
+        OutputFormat::Json => {
+            let records: Vec<OutputRecord> = records.collect();
+            output = serde_json::to_string_pretty(&records).unwrap() + "\n";
+        }
+        OutputFormat::Jsonl => {
+            for record in records {
+                output.push_str(&serde_json::to_string(&record).unwrap());
+                output.push('\n');
+            }
+        }
+        OutputFormat::Csv => {
+            output.push_str(&COLUMNS.join(","));
+            output.push('\n');
+            for record in records {
+                let fields = record.columns().map(|field| csv_field(&field));
+                output.push_str(&fields.join(","));
+                output.push('\n');
+            }
+        }
+        OutputFormat::Tsv => {
+            output.push_str(&COLUMNS.join("\t"));
+            output.push('\n');
+            for record in records {
+                let fields = record.columns().map(|field| tsv_field(&field));
+                output.push_str(&fields.join("\t"));
+                output.push('\n');
+            }
+        }
+        OutputFormat::EmojiOnly => {
+            for result in results {
+                output.push_str(&result.emoji);
+                output.push('\n');
+            }
+        }
+    }
+
+    output
+}
+
+/// Quote a CSV field if needed, as in RFC 4180
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Escape backslashes, tabs and line breaks in a TSV field
+fn tsv_field(field: &str) -> String {
+    field
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+/// A user-defined line printed for each result, e.g. `{emoji}\t{name}`
+///
+/// `{{` and `}}` print literal braces, and `\t`, `\n` and `\\` print a tab,
+/// line break and backslash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    parts: Vec<TemplatePart>,
+    /// Source code escape applied to `{emoji}`
+    escape: Option<EscapeFormat>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TemplatePart {
+    Literal(String),
+    Placeholder(Placeholder),
+}
+
+/// A value of a result that a template can print
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Placeholder {
+    Emoji,
+    Term,
+    Name,
+    Shortcode,
+    Shortcodes,
+    Group,
+    Subgroup,
+    Source,
+    Stage,
+    Score,
+    Codepoints,
+}
+
+/// Placeholder names, in the order they are documented
+const PLACEHOLDERS: [(&str, Placeholder); 11] = [
+    ("emoji", Placeholder::Emoji),
+    ("term", Placeholder::Term),
+    ("name", Placeholder::Name),
+    ("shortcode", Placeholder::Shortcode),
+    ("shortcodes", Placeholder::Shortcodes),
+    ("group", Placeholder::Group),
+    ("subgroup", Placeholder::Subgroup),
+    ("source", Placeholder::Source),
+    ("stage", Placeholder::Stage),
+    ("score", Placeholder::Score),
+    ("codepoints", Placeholder::Codepoints),
+];
+
+/// Why a template could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    /// A `{name}` that is not a known placeholder
+    UnknownPlaceholder(String),
+    /// A `{` without its closing `}`
+    UnclosedPlaceholder,
+    /// A `}` that does not close a placeholder; `}}` prints a literal brace
+    UnmatchedBrace,
+}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateError::UnknownPlaceholder(name) => {
+                let names: Vec<String> = PLACEHOLDERS
+                    .iter()
+                    .map(|(name, _)| format!("{{{}}}", name))
+                    .collect();
+                write!(
+                    f,
+                    "unknown placeholder '{{{}}}' (expected one of {})",
+                    name,
+                    names.join(", ")
+                )
+            }
+            TemplateError::UnclosedPlaceholder => write!(f, "missing '}}' after '{{'"),
+            TemplateError::UnmatchedBrace => {
+                write!(f, "unmatched '}}' (use '}}}}' for a literal brace)")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+impl Template {
+    /// Parse a template, reporting unknown placeholders and stray braces
+    pub fn parse(template: &str) -> Result<Self, TemplateError> {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.next_if_eq(&'{').is_some() => literal.push('{'),
+                '}' if chars.next_if_eq(&'}').is_some() => literal.push('}'),
+                '}' => return Err(TemplateError::UnmatchedBrace),
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err(TemplateError::UnclosedPlaceholder),
+                        }
+                    }
+                    let placeholder = PLACEHOLDERS
+                        .iter()
+                        .find(|(known, _)| *known == name)
+                        .map(|(_, placeholder)| *placeholder)
+                        .ok_or(TemplateError::UnknownPlaceholder(name))?;
+
+                    if !literal.is_empty() {
+                        parts.push(TemplatePart::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(TemplatePart::Placeholder(placeholder));
+                }
+                '\\' => match chars.next_if(|c| matches!(c, 't' | 'n' | '\\')) {
+                    Some('t') => literal.push('\t'),
+                    Some('n') => literal.push('\n'),
+                    // `\\`, or a lone backslash kept as written
+                    _ => literal.push('\\'),
+                },
+                c => literal.push(c),
+            }
+        }
+
+        if !literal.is_empty() {
+            parts.push(TemplatePart::Literal(literal));
+        }
+        Ok(Template {
+            parts,
+            escape: None,
+        })
+    }
+
+    /// Print `{emoji}` as a source code literal, leaving the other placeholders as they are
+    pub fn escaped(self, escape: Option<EscapeFormat>) -> Self {
+        Template { escape, ..self }
+    }
+
+    /// Fill in the placeholders for a result, without a trailing newline
+    pub fn render(&self, result: &SearchResult) -> String {
+        let unicode = result.matched.unicode();
+        let shortcodes = || -> Vec<&str> {
+            unicode
+                .map(|emoji| emoji.shortcodes().collect())
+                .unwrap_or_default()
+        };
+
+        self.parts
+            .iter()
+            .map(|part| match part {
+                TemplatePart::Literal(text) => text.clone(),
+                TemplatePart::Placeholder(placeholder) => match placeholder {
+                    Placeholder::Emoji => match self.escape {
+                        Some(format) => escape(&result.emoji, format),
+                        None => result.emoji.clone(),
+                    },
+                    Placeholder::Term => result.term.clone(),
+                    Placeholder::Name => match_name(&result.matched),
+                    Placeholder::Shortcode => shortcodes()
+                        .first()
+                        .copied()
+                        .unwrap_or_default()
+                        .to_string(),
+                    Placeholder::Shortcodes => shortcodes().join(" "),
+                    Placeholder::Group => unicode
+                        .map(|emoji| group_name(emoji.group()))
+                        .unwrap_or_default()
+                        .to_string(),
+                    Placeholder::Subgroup => unicode
+                        .and_then(subgroup_of)
+                        .unwrap_or_default()
+                        .to_string(),
+                    Placeholder::Source => result.source.as_str().to_string(),
+                    Placeholder::Stage => result.stage.as_str().to_string(),
+                    Placeholder::Score => result.score.to_string(),
+                    Placeholder::Codepoints => inspect(&result.emoji).sequence,
+                },
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::{SearchOptions, Searcher};
+
+    fn search(query: &str) -> Vec<SearchResult> {
+        Searcher::new(SearchOptions {
+            limit: 1,
+            ..Default::default()
+        })
+        .search(query)
+    }
+
+    #[test]
+    fn test_record() {
+        let record = OutputRecord::from_result(&search("pizza")[0]);
+        assert_eq!(record.emoji, "🍕");
+        assert_eq!(record.name, "pizza");
+        assert_eq!(record.shortcodes, vec!["pizza"]);
+        assert_eq!(record.group, Some("food-and-drink"));
+        assert_eq!(record.source, MatchSource::Name);
+
+        let combo = OutputRecord::from_result(&search("gcp")[0]);
+        assert_eq!(combo.emoji, "🔵🔴🟡");
+        assert_eq!(combo.name, "blue circle + red circle + yellow circle");
+        assert_eq!(combo.group, None);
+        assert!(combo.shortcodes.is_empty());
+    }
+
+    #[test]
+    fn test_combo_name() {
+        let matched = EmojiMatch::Sequence("💅👑");
+        assert_eq!(match_name(&matched), "nail polish + crown");
+    }
+
+    #[test]
+    fn test_formats() {
+        let results = search("pizza");
+        assert_eq!(format_results(&results, OutputFormat::EmojiOnly), "🍕\n");
+        assert_eq!(
+            format_results(&results, OutputFormat::Csv),
+            "emoji,term,name,shortcodes,group,source,score\n🍕,pizza,pizza,pizza,food-and-drink,name,8500\n"
+        );
+        let line = format_results(&results, OutputFormat::Jsonl);
+        let record: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(record["group"], "food-and-drink");
+        assert_eq!(format_results(&[], OutputFormat::Json), "[]\n");
+    }
+
+    #[test]
+    fn test_template() {
+        let template =
+            Template::parse(r"{emoji}\t{name}\t:{shortcode}:\t{codepoints} {{{group}}}").unwrap();
+        assert_eq!(
+            template.render(&search("pizza")[0]),
+            "🍕\tpizza\t:pizza:\tU+1F355 {food-and-drink}"
+        );
+        let combo = Template::parse("{shortcodes}|{subgroup}|{stage}").unwrap();
+        assert_eq!(combo.render(&search("gcp")[0]), "||exact_slang");
+    }
+
+    #[test]
+    fn test_template_with_escape() {
+        let template = Template::parse("{emoji} {codepoints} {name}")
+            .unwrap()
+            .escaped(Some(EscapeFormat::Rust));
+        assert_eq!(
+            template.render(&search("fire")[0]),
+            r#""\u{1F525}" U+1F525 fire"#
+        );
+    }
+
+    #[test]
+    fn test_template_errors() {
+        assert_eq!(
+            Template::parse("{emoji} {glyph}"),
+            Err(TemplateError::UnknownPlaceholder("glyph".to_string()))
+        );
+        assert_eq!(
+            Template::parse("{emoji"),
+            Err(TemplateError::UnclosedPlaceholder)
+        );
+        assert_eq!(
+            Template::parse("emoji}"),
+            Err(TemplateError::UnmatchedBrace)
+        );
+    }
+
+    #[test]
+    fn test_field_escaping() {
+        assert_eq!(csv_field("a, \"b\""), "\"a, \"\"b\"\"\"");
+        assert_eq!(tsv_field("a\tb\\"), "a\\tb\\\\");
+    }
+}
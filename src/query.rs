@@ -0,0 +1,311 @@
+use crate::model::MatchSource;
+use std::fmt;
+
+/// A search expression parsed from the query syntax
+///
+/// - `heart -broken`: words are combined, `-` excludes emojis matching a word
+/// - `cat|dog`: `|` separates alternatives whose results are merged
+/// - `"smiling face"`: quotes keep the words together as a phrase
+/// - `sc:thumbsup`, `slang:cap`, `name:cat`: only match that source
+///
+/// Other `word:` prefixes (e.g. `flag: japan`) are searched as they are, and so is an
+/// alternative made only of `-` words (e.g. the `-1` shortcode).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    pub(crate) alternatives: Vec<Alternative>,
+}
+
+/// Terms that must all hold for a result, one side of a `|`
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct Alternative {
+    pub terms: Vec<Term>,
+}
+
+/// A single word or quoted phrase
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct Term {
+    /// Lowercased word or phrase
+    pub text: String,
+    /// Whether the term was quoted
+    pub phrase: bool,
+    /// Whether the term was prefixed with `-`
+    pub negated: bool,
+    /// The source the term is restricted to by a field prefix
+    pub field: Option<MatchSource>,
+}
+
+/// Why a query could not be parsed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryError {
+    /// A `"` without its closing quote
+    UnterminatedQuote,
+    /// A pair of quotes with nothing in between
+    EmptyPhrase,
+    /// Nothing before or after a `|`
+    EmptyAlternative,
+    /// A `-` or field prefix without a term after it
+    MissingTerm(String),
+    /// A field prefix on an excluded term
+    NegatedField(String),
+    /// Different field prefixes within one alternative
+    ConflictingFields,
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QueryError::UnterminatedQuote => write!(f, "missing closing quote"),
+            QueryError::EmptyPhrase => write!(f, "empty quoted phrase"),
+            QueryError::EmptyAlternative => write!(f, "empty alternative around '|'"),
+            QueryError::MissingTerm(prefix) => write!(f, "missing term after '{}'", prefix),
+            QueryError::NegatedField(field) => {
+                write!(f, "field prefix '{}:' cannot be excluded with '-'", field)
+            }
+            QueryError::ConflictingFields => {
+                write!(f, "different field prefixes cannot be combined without '|'")
+            }
+        }
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+impl Query {
+    /// Parse a query, reporting malformed syntax
+    pub fn parse(input: &str) -> Result<Self, QueryError> {
+        let mut alternatives = Vec::new();
+        let mut current = Alternative::default();
+        let mut chars = input.chars().peekable();
+
+        loop {
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+
+            match chars.peek() {
+                None => break,
+                Some('|') => {
+                    chars.next();
+                    alternatives.push(current.finish()?);
+                    current = Alternative::default();
+                    continue;
+                }
+                Some(_) => {}
+            }
+
+            let mut prefix = String::new();
+            // A `-` before a digit is part of the word, as in the `-1` shortcode
+            let negated = chars.peek() == Some(&'-')
+                && chars.clone().nth(1).is_none_or(|c| !c.is_ascii_digit())
+                && chars.next().is_some();
+            if negated {
+                prefix.push('-');
+            }
+
+            // Read a word up to whitespace, `|` or an opening quote
+            let mut word = String::new();
+            while let Some(c) = chars.next_if(|c| !c.is_whitespace() && *c != '|' && *c != '"') {
+                word.push(c);
+            }
+
+            let mut field = None;
+            if let Some((name, rest)) = word.split_once(':') {
+                if let Some(source) = parse_field(name) {
+                    field = Some(source);
+                    if negated {
+                        return Err(QueryError::NegatedField(name.to_string()));
+                    }
+                    prefix.push_str(name);
+                    prefix.push(':');
+                    word = rest.to_string();
+                }
+            }
+
+            let phrase = word.is_empty() && chars.next_if_eq(&'"').is_some();
+            let text = if phrase {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some(c) => text.push(c),
+                        None => return Err(QueryError::UnterminatedQuote),
+                    }
+                }
+                text.split_whitespace().collect::<Vec<_>>().join(" ")
+            } else {
+                word
+            };
+
+            if text.is_empty() {
+                if phrase {
+                    return Err(QueryError::EmptyPhrase);
+                }
+                return Err(QueryError::MissingTerm(prefix));
+            }
+
+            current.terms.push(Term {
+                text: text.to_lowercase(),
+                phrase,
+                negated,
+                field,
+            });
+        }
+
+        if !alternatives.is_empty() || !current.terms.is_empty() {
+            alternatives.push(current.finish()?);
+        }
+
+        Ok(Query { alternatives })
+    }
+
+    /// Whether the query is just words, with none of the query syntax
+    pub fn is_plain(&self) -> bool {
+        match self.alternatives.as_slice() {
+            [alternative] => alternative
+                .terms
+                .iter()
+                .all(|term| !term.phrase && !term.negated && term.field.is_none()),
+            _ => false,
+        }
+    }
+}
+
+impl Alternative {
+    /// Check the alternative can be searched
+    ///
+    /// With nothing but excluded terms, the terms are searched as literal `-word` text.
+    fn finish(mut self) -> Result<Self, QueryError> {
+        if self.terms.is_empty() {
+            return Err(QueryError::EmptyAlternative);
+        }
+        if self.terms.iter().all(|term| term.negated) {
+            for term in &mut self.terms {
+                term.negated = false;
+                term.text.insert(0, '-');
+            }
+        }
+        let mut fields = self.terms.iter().filter_map(|term| term.field);
+        if let Some(first) = fields.next() {
+            if fields.any(|field| field != first) {
+                return Err(QueryError::ConflictingFields);
+            }
+        }
+        Ok(self)
+    }
+
+    /// The source all results must come from, if restricted by a field prefix
+    pub fn field(&self) -> Option<MatchSource> {
+        self.terms.iter().find_map(|term| term.field)
+    }
+
+    /// The words to search for, joined by spaces
+    pub fn search_text(&self) -> String {
+        self.terms
+            .iter()
+            .filter(|term| !term.negated)
+            .map(|term| term.text.as_str())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// Map a field prefix to the match source it restricts to, if it is one
+fn parse_field(name: &str) -> Option<MatchSource> {
+    match name.to_lowercase().as_str() {
+        "name" => Some(MatchSource::Name),
+        "sc" | "shortcode" => Some(MatchSource::Shortcode),
+        "slang" => Some(MatchSource::Slang),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(text: &str) -> Term {
+        Term {
+            text: text.to_string(),
+            phrase: false,
+            negated: false,
+            field: None,
+        }
+    }
+
+    #[test]
+    fn test_plain_words() {
+        let query = Query::parse("Red  heart").unwrap();
+        assert!(query.is_plain());
+        assert_eq!(
+            query.alternatives[0].terms,
+            vec![term("red"), term("heart")]
+        );
+    }
+
+    #[test]
+    fn test_syntax() {
+        let query = Query::parse("heart -broken | sc:thumbsup | \"smiling  face\"").unwrap();
+        assert!(!query.is_plain());
+
+        let [heart, thumbsup, smiling] = query.alternatives.as_slice() else {
+            panic!("expected three alternatives");
+        };
+        assert_eq!(heart.terms[1].text, "broken");
+        assert!(heart.terms[1].negated);
+        assert_eq!(heart.search_text(), "heart");
+        assert_eq!(thumbsup.field(), Some(MatchSource::Shortcode));
+        assert_eq!(thumbsup.search_text(), "thumbsup");
+        assert!(smiling.terms[0].phrase);
+        assert_eq!(smiling.terms[0].text, "smiling face");
+    }
+
+    #[test]
+    fn test_shortcode_colons_are_not_fields() {
+        assert!(Query::parse(":thumbsup:").unwrap().is_plain());
+    }
+
+    #[test]
+    fn test_other_colons_are_words() {
+        let query = Query::parse("flag: japan").unwrap();
+        assert!(query.is_plain());
+        assert_eq!(query.alternatives[0].search_text(), "flag: japan");
+        assert_eq!(
+            Query::parse("flag:japan").unwrap().alternatives[0].terms,
+            vec![term("flag:japan")]
+        );
+        assert!(Query::parse("keycap: #").unwrap().is_plain());
+    }
+
+    #[test]
+    fn test_only_negated_terms_are_literal() {
+        assert_eq!(
+            Query::parse("-1").unwrap().alternatives[0].terms,
+            vec![term("-1")]
+        );
+        assert_eq!(
+            Query::parse("-broken").unwrap().alternatives[0].terms,
+            vec![term("-broken")]
+        );
+        assert!(Query::parse("heart -1").unwrap().is_plain());
+    }
+
+    #[test]
+    fn test_malformed_queries() {
+        assert_eq!(
+            Query::parse("\"smiling face"),
+            Err(QueryError::UnterminatedQuote)
+        );
+        assert_eq!(Query::parse("cat|"), Err(QueryError::EmptyAlternative));
+        assert_eq!(Query::parse("cat||dog"), Err(QueryError::EmptyAlternative));
+        assert_eq!(
+            Query::parse("heart -"),
+            Err(QueryError::MissingTerm("-".to_string()))
+        );
+        assert_eq!(
+            Query::parse("sc:"),
+            Err(QueryError::MissingTerm("sc:".to_string()))
+        );
+        assert_eq!(
+            Query::parse("sc:cat slang:cat"),
+            Err(QueryError::ConflictingFields)
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use crate::facet::Facets;
+use crate::model::{Match, MatchStage};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+/// Points separating one match stage from the next
+///
+/// Adjustments within a stage are always smaller, so a better stage always ranks higher.
+const STAGE_POINTS: u32 = 1000;
+
+/// Points available for how close the matched term is to the query
+const CLOSENESS_POINTS: u32 = 500;
+
+/// Score a candidate
+///
+/// `rank` orders otherwise equal candidates, 0 being best (e.g. the position of an
+/// emoji within a slang term, or name before shortcode).
+pub(crate) fn score(stage: MatchStage, query: &str, term: &str, rank: usize) -> u32 {
+    let stage_points = (MatchStage::Browse as u32 + 1 - stage as u32) * STAGE_POINTS;
+    let distance = query.chars().count().abs_diff(term.chars().count()).min(40) as u32;
+    stage_points + CLOSENESS_POINTS - distance * 10 - rank.min(9) as u32
+}
+
+/// Candidates collected across all stages, keeping the best score per emoji
+#[derive(Default)]
+pub(crate) struct Candidates {
+    matches: Vec<Match>,
+    by_emoji: HashMap<&'static str, usize>,
+    facets: Facets,
+}
+
+impl Candidates {
+    /// Collect only candidates passing the facets
+    pub fn new(facets: Facets) -> Self {
+        Self {
+            facets,
+            ..Default::default()
+        }
+    }
+
+    /// Add a candidate, replacing an earlier one for the same emoji if this scores higher
+    ///
+    /// Candidates outside the facets are dropped.
+    pub fn offer(&mut self, key: &'static str, candidate: Match) {
+        if !self.facets.allows_match(&candidate.emoji) {
+            return;
+        }
+
+        match self.by_emoji.get(key) {
+            Some(&i) => {
+                if candidate.score > self.matches[i].score {
+                    self.matches[i] = candidate;
+                }
+            }
+            None => {
+                self.by_emoji.insert(key, self.matches.len());
+                self.matches.push(candidate);
+            }
+        }
+    }
+
+    /// Number of distinct emojis offered so far
+    pub fn len(&self) -> usize {
+        self.matches.len()
+    }
+
+    /// Whether a candidate for the emoji was already offered
+    pub fn contains(&self, key: &str) -> bool {
+        self.by_emoji.contains_key(key)
+    }
+
+    /// Sort by descending score and keep the best `limit` matches
+    ///
+    /// The sort is stable, so ties keep index order: Unicode CLDR order for emojis
+    /// and alphabetical order for slang terms.
+    pub fn into_ranked(mut self, limit: usize) -> Vec<Match> {
+        self.matches.sort_by_key(|m| Reverse(m.score));
+        self.matches.truncate(limit);
+        self.matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stage_outranks_closeness() {
+        let worst_exact = score(MatchStage::Exact, "a", &"a".repeat(60), 9);
+        let best_prefix_slang = score(MatchStage::PrefixSlang, "fire", "fire", 0);
+        assert!(worst_exact > best_prefix_slang);
+    }
+
+    #[test]
+    fn test_closer_terms_score_higher() {
+        let fire = score(MatchStage::PrefixSlang, "fir", "fire", 0);
+        let firework = score(MatchStage::PrefixSlang, "fir", "fireworks", 0);
+        assert!(fire > firework);
+    }
+}
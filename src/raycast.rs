@@ -0,0 +1,119 @@
+use crate::model::SearchResult;
+use crate::output::match_name;
+use crate::query::QueryError;
+use serde::Serialize;
+
+/// A row for a Raycast `List`, with the props of `List.Item` plus the emoji to act on
+#[derive(Serialize, Debug)]
+pub struct RaycastItem {
+    pub id: String,
+    pub title: String,
+    pub subtitle: String,
+    /// Emoji used as the item icon; Raycast renders emoji strings as icons
+    pub icon: String,
+    /// Extra words Raycast's own filtering matches, here the shortcodes
+    pub keywords: Vec<String>,
+    pub accessories: Vec<RaycastAccessory>,
+    /// The emoji to copy or paste, absent for placeholder rows
+    pub emoji: Option<String>,
+}
+
+/// Text shown on the right side of a Raycast list item
+#[derive(Serialize, Debug)]
+pub struct RaycastAccessory {
+    pub text: String,
+}
+
+/// Top-level JSON document for a Raycast extension's list
+#[derive(Serialize, Debug)]
+pub struct RaycastResponse {
+    pub items: Vec<RaycastItem>,
+}
+
+impl RaycastItem {
+    /// Build a Raycast item for a search result
+    pub fn from_result(result: SearchResult) -> Self {
+        let shortcodes = result
+            .matched
+            .unicode()
+            .map(|emoji| emoji.shortcodes().map(String::from).collect())
+            .unwrap_or_default();
+
+        RaycastItem {
+            id: format!("{}-{}", result.term, result.emoji),
+            title: result.term,
+            subtitle: match_name(&result.matched),
+            icon: result.emoji.clone(),
+            keywords: shortcodes,
+            accessories: vec![RaycastAccessory {
+                text: result.source.as_str().to_string(),
+            }],
+            emoji: Some(result.emoji),
+        }
+    }
+}
+
+impl RaycastResponse {
+    /// Build a Raycast response listing the search results
+    pub fn from_results(results: Vec<SearchResult>) -> Self {
+        RaycastResponse {
+            items: results.into_iter().map(RaycastItem::from_result).collect(),
+        }
+    }
+
+    /// Empty response, so Raycast shows the list's empty view
+    pub fn no_results() -> Self {
+        RaycastResponse { items: Vec::new() }
+    }
+
+    /// Create a response explaining why the query could not be parsed
+    pub fn invalid_query(err: &QueryError) -> Self {
+        RaycastResponse {
+            items: vec![RaycastItem {
+                id: "invalid-query".to_string(),
+                title: "Invalid query".to_string(),
+                subtitle: err.to_string(),
+                icon: "⚠️".to_string(),
+                keywords: Vec::new(),
+                accessories: Vec::new(),
+                emoji: None,
+            }],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::Searcher;
+
+    #[test]
+    fn test_result_items() {
+        let results = Searcher::default().search("slay");
+        let response = RaycastResponse::from_results(results);
+
+        let nails = &response.items[0];
+        assert_eq!(nails.id, "slay-💅");
+        assert_eq!(nails.title, "slay");
+        assert_eq!(nails.subtitle, "nail polish");
+        assert_eq!(nails.icon, "💅");
+        assert_eq!(nails.keywords, vec!["nail_care"]);
+        assert_eq!(nails.accessories[0].text, "slang");
+        assert_eq!(nails.emoji.as_deref(), Some("💅"));
+
+        let combo = response.items.iter().find(|i| i.icon == "💅👑").unwrap();
+        assert_eq!(combo.subtitle, "nail polish + crown");
+        assert!(combo.keywords.is_empty());
+    }
+
+    #[test]
+    fn test_placeholder_responses() {
+        let json = serde_json::to_value(RaycastResponse::no_results()).unwrap();
+        assert_eq!(json, serde_json::json!({ "items": [] }));
+
+        let err = QueryError::UnterminatedQuote;
+        let json = serde_json::to_value(RaycastResponse::invalid_query(&err)).unwrap();
+        assert_eq!(json["items"][0]["subtitle"], "missing closing quote");
+        assert!(json["items"][0]["emoji"].is_null());
+    }
+}
@@ -0,0 +1,105 @@
+use crate::listing::Listing;
+
+/// Separates the option name and value of a rofi script-mode row
+const FIELD_SEPARATOR: char = '\u{1f}';
+
+/// How rofi invoked a script-mode script, from the `ROFI_RETV` variable
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RofiState {
+    /// The first call, listing the initial rows
+    Initial,
+    /// A row was selected; its text is the argument and its info is in `ROFI_INFO`
+    Selected,
+    /// Text that matched no row was entered; it is the argument
+    CustomInput,
+}
+
+impl RofiState {
+    /// Read the state from the value of `ROFI_RETV`
+    pub fn from_retv(retv: Option<&str>) -> Self {
+        match retv {
+            // 10-28 are the custom keybindings kb-custom-1 to kb-custom-19
+            Some("1") => RofiState::Selected,
+            Some(retv) if retv.parse::<u32>().is_ok_and(|retv| retv >= 10) => RofiState::Selected,
+            Some("2") => RofiState::CustomInput,
+            _ => RofiState::Initial,
+        }
+    }
+}
+
+/// Rows setting rofi's prompt and an optional message above the list
+pub fn rofi_header(prompt: &str, message: Option<&str>) -> String {
+    let mut header = format!("\0prompt{}{}\n", FIELD_SEPARATOR, prompt);
+    if let Some(message) = message {
+        header.push_str(&format!("\0message{}{}\n", FIELD_SEPARATOR, message));
+    }
+    header
+}
+
+/// A rofi script-mode row showing the emoji and name, filterable by its keywords
+///
+/// The emoji is kept in the row's info so it survives any display changes.
+pub fn rofi_row(listing: &Listing) -> String {
+    format!(
+        "{} {}\0info{sep}{}{sep}meta{sep}{}\n",
+        listing.emoji,
+        listing.name,
+        listing.emoji,
+        listing.keywords(),
+        sep = FIELD_SEPARATOR
+    )
+}
+
+/// A line for dmenu, wofi or fuzzel: the emoji, its name and keywords
+pub fn dmenu_line(listing: &Listing) -> String {
+    let keywords = listing.keywords();
+    if keywords.is_empty() {
+        format!("{} {}", listing.emoji, listing.name)
+    } else {
+        format!("{} {}  {}", listing.emoji, listing.name, keywords)
+    }
+}
+
+/// The emoji of a selected row or line, its first space separated word
+pub fn selected_emoji(line: &str) -> Option<&str> {
+    line.split_whitespace().next()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::listing::list_results;
+    use crate::search::Searcher;
+
+    fn listing(query: &str) -> Listing {
+        let searcher = Searcher::default();
+        list_results(&searcher, &searcher.search(query)).remove(0)
+    }
+
+    #[test]
+    fn test_rofi_row() {
+        let row = rofi_row(&listing("pizza"));
+        assert_eq!(row, "🍕 pizza\0info\u{1f}🍕\u{1f}meta\u{1f}:pizza:\n");
+        assert_eq!(
+            rofi_header("emoji", Some("hi")),
+            "\0prompt\u{1f}emoji\n\0message\u{1f}hi\n"
+        );
+    }
+
+    #[test]
+    fn test_dmenu_line() {
+        let line = dmenu_line(&listing("lit"));
+        assert!(line.starts_with("🔥 fire  :fire: "));
+        assert!(line.contains(" lit"));
+        assert_eq!(selected_emoji(&line), Some("🔥"));
+    }
+
+    #[test]
+    fn test_rofi_state() {
+        assert_eq!(RofiState::from_retv(None), RofiState::Initial);
+        assert_eq!(RofiState::from_retv(Some("0")), RofiState::Initial);
+        assert_eq!(RofiState::from_retv(Some("1")), RofiState::Selected);
+        assert_eq!(RofiState::from_retv(Some("2")), RofiState::CustomInput);
+        assert_eq!(RofiState::from_retv(Some("12")), RofiState::Selected);
+    }
+}
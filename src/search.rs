@@ -0,0 +1,1060 @@
+use crate::facet::Facets;
+use crate::fuzzy::FuzzyMatcher;
+use crate::index::{normalize_relaxed, EmojiEntry, SlangEntry, INDEX};
+use crate::model::{
+    Comparison, EmojiMatch, Explanation, Match, MatchSource, MatchStage, SearchResult,
+};
+use crate::query::{Alternative, Query, QueryError};
+use crate::rank::{score, Candidates};
+use crate::variants::{apply_gender, apply_skin_tone, split_sequence, Gender, SkinTone};
+use std::collections::HashSet;
+
+/// Options controlling how a [`Searcher`] looks up and renders emojis
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// Maximum number of results to return
+    pub limit: usize,
+    /// Skin tone variant to apply to emojis that support it
+    pub skin_tone: Option<SkinTone>,
+    /// Gender variant to apply to emojis that support it
+    pub gender: Option<Gender>,
+    /// Tolerate typos when the other stages find fewer than `limit` matches
+    pub fuzzy: bool,
+    /// Only return emojis in these groups, subgroups and Unicode versions
+    pub facets: Facets,
+}
+
+impl Default for SearchOptions {
+    fn default() -> Self {
+        Self {
+            limit: 10,
+            skin_tone: None,
+            gender: None,
+            fuzzy: true,
+            facets: Facets::default(),
+        }
+    }
+}
+
+/// Emoji search engine using the same ranking as the `almoji` CLI
+///
+/// ```
+/// use almoji::{SearchOptions, Searcher};
+///
+/// let searcher = Searcher::new(SearchOptions { limit: 3, ..Default::default() });
+/// let results = searcher.search("fire");
+/// assert!(results.iter().any(|r| r.emoji == "🔥"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Searcher {
+    options: SearchOptions,
+}
+
+impl Searcher {
+    /// Create a searcher with the given options
+    pub fn new(options: SearchOptions) -> Self {
+        Self { options }
+    }
+
+    /// The options this searcher was created with
+    pub fn options(&self) -> &SearchOptions {
+        &self.options
+    }
+
+    /// Search for emojis matching the query
+    ///
+    /// A query ending with `+` combines all matches into a single result. Malformed
+    /// [`Query`] syntax finds nothing; use [`Searcher::try_search`] to see why. An empty
+    /// query lists the emojis in the configured facets, if any.
+    pub fn search(&self, query: &str) -> Vec<SearchResult> {
+        self.try_search(query).unwrap_or_default()
+    }
+
+    /// Search for emojis matching the query, reporting malformed query syntax
+    pub fn try_search(&self, query: &str) -> Result<Vec<SearchResult>, QueryError> {
+        let mut query_joined = query.to_string();
+
+        // Check if we should combine results (query ends with +)
+        let combine_results = query_joined.trim().ends_with('+');
+        if combine_results {
+            query_joined = query_joined.trim().trim_end_matches('+').trim().to_string();
+        }
+
+        let results = if query_joined.is_empty() {
+            if self.options.facets.is_empty() {
+                return Ok(Vec::new());
+            }
+            browse(&self.options)
+        } else {
+            search_query(&Query::parse(&query_joined)?, &self.options)
+        };
+
+        let processed_results: Vec<SearchResult> = results
+            .into_iter()
+            .map(|hit| SearchResult {
+                emoji: self.render(&hit.emoji),
+                term: hit.term,
+                stage: hit.stage,
+                source: hit.source,
+                score: hit.score,
+                explanation: hit.explanation,
+                matched: hit.emoji,
+            })
+            .collect();
+
+        if combine_results && !processed_results.is_empty() {
+            let combined_emojis: String = processed_results
+                .iter()
+                .map(|result| result.emoji.as_str())
+                .collect();
+            let first = &processed_results[0];
+            return Ok(vec![SearchResult {
+                emoji: combined_emojis,
+                term: query_joined,
+                stage: first.stage,
+                source: first.source,
+                score: first.score,
+                explanation: first.explanation.clone(),
+                matched: EmojiMatch::Combined(
+                    processed_results
+                        .iter()
+                        .map(|r| r.matched.clone())
+                        .collect(),
+                ),
+            }]);
+        }
+
+        Ok(processed_results)
+    }
+
+    /// Render a match with the configured skin tone and gender modifiers
+    pub(crate) fn render(&self, matched: &EmojiMatch) -> String {
+        match matched {
+            EmojiMatch::Unicode(emoji) => {
+                let mut modified_emoji = emoji.as_str().to_string();
+                if let Some(ref st) = self.options.skin_tone {
+                    modified_emoji = apply_skin_tone(emoji, st);
+                }
+                if let Some(ref g) = self.options.gender {
+                    modified_emoji = apply_gender(&modified_emoji, g);
+                }
+                modified_emoji
+            }
+            // Apply modifiers to each emoji of a slang combo individually
+            EmojiMatch::Sequence(sequence) => split_sequence(sequence)
+                .into_iter()
+                .map(|part| match part {
+                    Ok(emoji) => self.render(&EmojiMatch::Unicode(emoji)),
+                    Err(text) => text.to_string(),
+                })
+                .collect(),
+            EmojiMatch::Combined(parts) => parts.iter().map(|part| self.render(part)).collect(),
+        }
+    }
+}
+
+/// Check containment with relaxed normalization to catch close substring matches
+///
+/// `haystack_relaxed` and `needle_relaxed` are the precomputed relaxed forms.
+/// Returns how the strings matched, with the pair that was compared.
+fn relaxed_contains(
+    haystack: &'static str,
+    haystack_relaxed: &'static str,
+    needle: &str,
+    needle_relaxed: &str,
+) -> Option<Explanation> {
+    let (field, query, comparison) = if haystack.contains(needle) {
+        (haystack, needle, Comparison::Contains)
+    } else if haystack_relaxed.contains(needle_relaxed) {
+        (
+            haystack_relaxed,
+            needle_relaxed,
+            Comparison::RelaxedContains,
+        )
+    } else if needle_relaxed.contains(haystack_relaxed) {
+        (
+            haystack_relaxed,
+            needle_relaxed,
+            Comparison::RelaxedContainedIn,
+        )
+    } else {
+        return None;
+    };
+
+    Some(Explanation {
+        field,
+        query: query.to_string(),
+        comparison,
+    })
+}
+
+/// Whether the matched field contains every word of a multi-word query
+///
+/// The prefix and substring stages compare the words joined up, so without this a field
+/// matching just one word (e.g. "cat" for "cat happy") would be a result.
+fn has_every_word(field: &str, words: &[&str]) -> bool {
+    words.len() < 2 || words.iter().all(|word| field.contains(word))
+}
+
+/// A name or shortcode of a Unicode emoji that matched the query
+struct Hit {
+    term: String,
+    source: MatchSource,
+    /// Orders hits within a stage, 0 being best
+    rank: usize,
+    explanation: Explanation,
+}
+
+impl Hit {
+    /// A hit on the emoji's name; the term is the name without spaces
+    fn name(entry: &EmojiEntry, rank: usize, explanation: Explanation) -> Self {
+        Hit {
+            term: entry.name.replace(' ', ""),
+            source: MatchSource::Name,
+            rank,
+            explanation,
+        }
+    }
+
+    /// A hit on one of the emoji's shortcodes
+    fn shortcode(term: &str, rank: usize, explanation: Explanation) -> Self {
+        Hit {
+            term: term.to_string(),
+            source: MatchSource::Shortcode,
+            rank,
+            explanation,
+        }
+    }
+}
+
+/// Explanation for a plain comparison of the field with the lowercased query
+fn compared(field: &'static str, query: &str, comparison: Comparison) -> Explanation {
+    Explanation {
+        field,
+        query: query.to_string(),
+        comparison,
+    }
+}
+
+/// Offer the emojis of a slang term as candidates
+fn offer_slang(
+    candidates: &mut Candidates,
+    entry: &SlangEntry,
+    stage: MatchStage,
+    query: &str,
+    explanation: Explanation,
+) {
+    for (position, (value, emoji)) in entry.emojis().enumerate() {
+        candidates.offer(
+            value,
+            Match {
+                emoji,
+                term: entry.term.to_string(),
+                stage,
+                source: MatchSource::Slang,
+                score: score(stage, query, entry.term, position),
+                explanation: explanation.clone(),
+            },
+        );
+    }
+}
+
+/// Offer a Unicode emoji as a candidate
+fn offer_emoji(
+    candidates: &mut Candidates,
+    entry: &EmojiEntry,
+    stage: MatchStage,
+    query: &str,
+    hit: Hit,
+) {
+    candidates.offer(
+        entry.text,
+        Match {
+            emoji: EmojiMatch::Unicode(entry.emoji()),
+            score: score(stage, query, &hit.term, hit.rank),
+            term: hit.term,
+            stage,
+            source: hit.source,
+            explanation: hit.explanation,
+        },
+    );
+}
+
+/// Search for emojis matching the query using the comprehensive Unicode emoji database
+///
+/// The words of a multi-word query are matched both joined up and independently of
+/// their order. Every candidate from every stage is scored, then the best `limit` are
+/// returned, so identical queries always produce identical results.
+pub fn search_emojis(query: &str, limit: usize) -> Vec<Match> {
+    let options = SearchOptions {
+        limit,
+        ..Default::default()
+    };
+    search_stages(query, &options, Sources::ALL).into_ranked(limit)
+}
+
+/// The match sources a search may produce, narrowed by field prefixes like `sc:`
+#[derive(Debug, Clone, Copy)]
+struct Sources(Option<MatchSource>);
+
+impl Sources {
+    const ALL: Sources = Sources(None);
+
+    fn allows(self, source: MatchSource) -> bool {
+        self.0.is_none_or(|only| only == source)
+    }
+}
+
+/// Run the search stages, collecting candidates that pass the facets
+///
+/// The fuzzy stage only runs if enabled in the options.
+fn search_stages(query: &str, options: &SearchOptions, sources: Sources) -> Candidates {
+    let index = &INDEX;
+    let lowercase = query.to_lowercase();
+    let words: Vec<&str> = lowercase.split_whitespace().collect();
+    // Every stage but the all words one sees the words joined up
+    let query_lower = words.concat();
+    let query_spaced = words.join(" ");
+    let query_relaxed = normalize_relaxed(&query_lower);
+    let allow_substring = query_relaxed.chars().count() > 2;
+    let mut candidates = Candidates::new(options.facets);
+
+    // 1. Check custom slang mappings first - exact match
+    let slang_exact = index.slang_exact(&query_lower);
+    if let Some(entry) = slang_exact.filter(|_| sources.allows(MatchSource::Slang)) {
+        let explanation = compared(entry.term, &query_lower, Comparison::Equals);
+        offer_slang(
+            &mut candidates,
+            entry,
+            MatchStage::ExactSlang,
+            &query_lower,
+            explanation,
+        );
+    }
+
+    // 2. Exact matches on standard emoji names, with or without the spaces
+    let spaced_exact = (words.len() > 1).then(|| index.emoji_exact(&query_spaced));
+    for entry in index
+        .emoji_exact(&query_lower)
+        .chain(spaced_exact.into_iter().flatten())
+    {
+        // Check name (e.g., "smiling face"), then shortcodes (e.g., ":smile:")
+        let name_equal = entry.name == query_lower || entry.name == query_spaced;
+        let hit = if name_equal && sources.allows(MatchSource::Name) {
+            let explanation = compared(entry.name, entry.name, Comparison::Equals);
+            Some(Hit::name(entry, 0, explanation))
+        } else {
+            entry
+                .shortcodes
+                .iter()
+                .filter(|_| sources.allows(MatchSource::Shortcode))
+                .find(|sc| sc.lower == query_lower)
+                .map(|sc| {
+                    let explanation = compared(sc.lower, &query_lower, Comparison::Equals);
+                    Hit::shortcode(sc.raw, 1, explanation)
+                })
+        };
+
+        if let Some(hit) = hit {
+            offer_emoji(&mut candidates, entry, MatchStage::Exact, &query_lower, hit);
+        }
+    }
+
+    // 3. Check custom slang mappings - prefix match
+    for entry in index.slang_prefix(&query_lower) {
+        // Skip exact matches (already handled)
+        if entry.term != query_lower
+            && sources.allows(MatchSource::Slang)
+            && has_every_word(entry.term, &words)
+        {
+            let explanation = compared(entry.term, &query_lower, Comparison::Prefix);
+            offer_slang(
+                &mut candidates,
+                entry,
+                MatchStage::PrefixSlang,
+                &query_lower,
+                explanation,
+            );
+        }
+    }
+
+    // 4. Prefix matches on standard emoji names
+    for entry in index.emoji_prefix(&query_lower) {
+        if candidates.contains(entry.text) {
+            continue;
+        }
+
+        // Check if name starts with query, or any word in the name does,
+        // then check shortcodes for prefix matches
+        let names = sources.allows(MatchSource::Name);
+        let hit = if names && entry.name.starts_with(&query_lower) {
+            let explanation = compared(entry.name, &query_lower, Comparison::Prefix);
+            Some(Hit::name(entry, 0, explanation))
+        } else if names
+            && entry
+                .name
+                .split_whitespace()
+                .any(|word| word.starts_with(&query_lower))
+        {
+            let explanation = compared(entry.name, &query_lower, Comparison::WordPrefix);
+            Some(Hit::name(entry, 1, explanation))
+        } else {
+            entry
+                .shortcodes
+                .iter()
+                .filter(|_| sources.allows(MatchSource::Shortcode))
+                .find(|sc| sc.lower.starts_with(&query_lower))
+                .map(|sc| {
+                    let explanation = compared(sc.lower, &query_lower, Comparison::Prefix);
+                    Hit::shortcode(sc.lower, 2, explanation)
+                })
+        };
+
+        if let Some(hit) = hit.filter(|hit| has_every_word(hit.explanation.field, &words)) {
+            offer_emoji(
+                &mut candidates,
+                entry,
+                MatchStage::Prefix,
+                &query_lower,
+                hit,
+            );
+        }
+    }
+
+    // 5. Multi-word queries matching every word, in any order
+    if words.len() > 1 && sources.allows(MatchSource::Name) {
+        for entry in index.emoji_all_words(&words) {
+            if candidates.contains(entry.text) {
+                continue;
+            }
+
+            let explanation = compared(entry.name, &query_spaced, Comparison::AllWords);
+            offer_emoji(
+                &mut candidates,
+                entry,
+                MatchStage::AllWords,
+                &query_lower,
+                Hit::name(entry, 0, explanation),
+            );
+        }
+    }
+
+    if !allow_substring {
+        return candidates;
+    }
+
+    // 6. Check custom slang mappings - substring match
+    for entry in index.slang {
+        // Skip exact and prefix matches (already handled)
+        if entry.term.starts_with(&query_lower)
+            || !sources.allows(MatchSource::Slang)
+            || !has_every_word(entry.term, &words)
+        {
+            continue;
+        }
+
+        // Check if slang term contains query
+        if let Some(explanation) =
+            relaxed_contains(entry.term, entry.relaxed, &query_lower, &query_relaxed)
+        {
+            offer_slang(
+                &mut candidates,
+                entry,
+                MatchStage::SubstringSlang,
+                &query_lower,
+                explanation,
+            );
+        }
+    }
+
+    // 7. Substring matches on standard emoji names
+    for entry in index.emojis {
+        if candidates.contains(entry.text) {
+            continue;
+        }
+
+        // Check if name contains query, then shortcodes for substring matches
+        let name = relaxed_contains(entry.name, entry.name_relaxed, &query_lower, &query_relaxed)
+            .filter(|_| sources.allows(MatchSource::Name));
+        let hit = match name {
+            Some(explanation) => Some(Hit::name(entry, 0, explanation)),
+            None => entry
+                .shortcodes
+                .iter()
+                .filter(|_| sources.allows(MatchSource::Shortcode))
+                .find_map(|sc| {
+                    relaxed_contains(sc.lower, sc.relaxed, &query_lower, &query_relaxed)
+                        .map(|explanation| Hit::shortcode(sc.lower, 1, explanation))
+                }),
+        };
+
+        if let Some(hit) = hit.filter(|hit| has_every_word(hit.explanation.field, &words)) {
+            offer_emoji(
+                &mut candidates,
+                entry,
+                MatchStage::Substring,
+                &query_lower,
+                hit,
+            );
+        }
+    }
+
+    // 8. Typo tolerant matches, only to fill up the remaining results
+    if options.fuzzy && candidates.len() < options.limit {
+        if let Some(mut matcher) = FuzzyMatcher::new(&query_relaxed) {
+            let query = (query_lower.as_str(), query_relaxed.as_str());
+            let name_words = words.len() == 1;
+            offer_fuzzy(&mut candidates, &mut matcher, query, sources, name_words);
+        }
+    }
+
+    candidates
+}
+
+/// Offer slang terms and emojis within a few edits of the query
+///
+/// Single name words are only compared with single word queries, as one name word close to
+/// the joined words of a multi-word query matches just one of them.
+fn offer_fuzzy(
+    candidates: &mut Candidates,
+    matcher: &mut FuzzyMatcher,
+    (query, query_relaxed): (&str, &str),
+    sources: Sources,
+    name_words: bool,
+) {
+    let fuzzy = |field| compared(field, query_relaxed, Comparison::Fuzzy);
+
+    for entry in INDEX.slang {
+        if sources.allows(MatchSource::Slang) && matcher.distance(entry.relaxed).is_some() {
+            offer_slang(
+                candidates,
+                entry,
+                MatchStage::Fuzzy,
+                query,
+                fuzzy(entry.relaxed),
+            );
+        }
+    }
+
+    for entry in INDEX.emojis {
+        if candidates.contains(entry.text) {
+            continue;
+        }
+
+        // Keep the closest of name, name words and shortcodes, preferring the name on ties
+        let mut best: Option<(usize, Hit)> = None;
+        let mut consider = |distance: Option<usize>, hit: &dyn Fn(usize) -> Hit| {
+            if let Some(distance) = distance {
+                if best.as_ref().is_none_or(|(d, _)| distance < *d) {
+                    best = Some((distance, hit(distance)));
+                }
+            }
+        };
+
+        if sources.allows(MatchSource::Name) {
+            consider(matcher.distance(entry.name_relaxed), &|d| {
+                Hit::name(entry, d * 3, fuzzy(entry.name_relaxed))
+            });
+            for word in entry.name.split_whitespace().filter(|_| name_words) {
+                consider(matcher.distance(word), &|d| {
+                    Hit::name(entry, d * 3 + 1, fuzzy(word))
+                });
+            }
+        }
+        if sources.allows(MatchSource::Shortcode) {
+            for sc in entry.shortcodes {
+                consider(matcher.distance(sc.relaxed), &|d| {
+                    Hit::shortcode(sc.lower, d * 3 + 2, fuzzy(sc.relaxed))
+                });
+            }
+        }
+
+        if let Some((_, hit)) = best {
+            offer_emoji(candidates, entry, MatchStage::Fuzzy, query, hit);
+        }
+    }
+}
+
+/// Search for a parsed query, applying its alternatives, phrases and exclusions
+fn search_query(query: &Query, options: &SearchOptions) -> Vec<Match> {
+    let limit = options.limit;
+    if let ([alternative], true) = (query.alternatives.as_slice(), query.is_plain()) {
+        let text = alternative.search_text();
+        return search_stages(&text, options, Sources::ALL).into_ranked(limit);
+    }
+
+    let mut merged = Candidates::default();
+    for alternative in &query.alternatives {
+        let sources = Sources(alternative.field());
+        let filter = TermFilter::new(alternative);
+        let matches =
+            search_stages(&alternative.search_text(), options, sources).into_ranked(usize::MAX);
+
+        for candidate in matches.into_iter().filter(|m| filter.accepts(m)) {
+            let key = match candidate.emoji {
+                EmojiMatch::Unicode(emoji) => emoji.as_str(),
+                EmojiMatch::Sequence(sequence) => sequence,
+                EmojiMatch::Combined(_) => unreachable!("stages never combine matches"),
+            };
+            merged.offer(key, candidate);
+        }
+    }
+
+    merged.into_ranked(limit)
+}
+
+/// List the emojis passing the facets, in CLDR order
+fn browse(options: &SearchOptions) -> Vec<Match> {
+    let score = score(MatchStage::Browse, "", "", 0);
+    INDEX
+        .emojis
+        .iter()
+        .filter(|entry| options.facets.allows(entry.emoji()))
+        .take(options.limit)
+        .map(|entry| Match {
+            emoji: EmojiMatch::Unicode(entry.emoji()),
+            term: entry.name.replace(' ', ""),
+            stage: MatchStage::Browse,
+            source: MatchSource::Name,
+            score,
+            explanation: compared(entry.name, "", Comparison::Any),
+        })
+        .collect()
+}
+
+/// Checks the phrases and excluded terms of an alternative against its matches
+struct TermFilter<'a> {
+    /// Phrases every match must mention
+    phrases: Vec<&'a str>,
+    /// Phrases no match may mention
+    excluded_phrases: Vec<&'a str>,
+    /// Words no match may have a name word, shortcode or slang term starting with
+    excluded_words: Vec<&'a str>,
+    /// Emojis matched by the excluded words
+    excluded_emojis: HashSet<&'static str>,
+}
+
+impl<'a> TermFilter<'a> {
+    fn new(alternative: &'a Alternative) -> Self {
+        let mut filter = TermFilter {
+            phrases: Vec::new(),
+            excluded_phrases: Vec::new(),
+            excluded_words: Vec::new(),
+            excluded_emojis: HashSet::new(),
+        };
+
+        for term in &alternative.terms {
+            match (term.negated, term.phrase) {
+                (false, false) => {}
+                (false, true) => filter.phrases.push(&term.text),
+                (true, true) => filter.excluded_phrases.push(&term.text),
+                (true, false) => {
+                    let words = [term.text.as_str()];
+                    let emojis = INDEX.emoji_all_words(&words).map(|entry| entry.text);
+                    filter.excluded_emojis.extend(emojis);
+                    filter.excluded_words.push(&term.text);
+                }
+            }
+        }
+
+        filter
+    }
+
+    fn accepts(&self, candidate: &Match) -> bool {
+        let excluded_word = match candidate.emoji {
+            EmojiMatch::Unicode(emoji) => self.excluded_emojis.contains(emoji.as_str()),
+            _ => candidate
+                .term
+                .split_whitespace()
+                .any(|word| self.excluded_words.iter().any(|ex| word.starts_with(ex))),
+        };
+
+        !excluded_word
+            && self
+                .phrases
+                .iter()
+                .all(|phrase| mentions(candidate, phrase))
+            && !self
+                .excluded_phrases
+                .iter()
+                .any(|phrase| mentions(candidate, phrase))
+    }
+}
+
+/// Whether the matched term or the emoji name contains the phrase
+fn mentions(candidate: &Match, phrase: &str) -> bool {
+    let in_name = candidate
+        .emoji
+        .unicode()
+        .is_some_and(|emoji| emoji.name().to_lowercase().contains(phrase));
+    in_name || normalize_relaxed(&candidate.term).contains(&normalize_relaxed(phrase))
+}
+
+/// Search for emojis matching a query given as separate words
+pub fn find_emojis(
+    query: &[String],
+    limit: usize,
+    skin_tone: &Option<SkinTone>,
+    gender: &Option<Gender>,
+) -> Vec<SearchResult> {
+    Searcher::new(SearchOptions {
+        limit,
+        skin_tone: *skin_tone,
+        gender: *gender,
+        ..Default::default()
+    })
+    .search(&query.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_match() {
+        let results = search_emojis("heart", 10);
+        assert!(!results.is_empty());
+    }
+
+    #[test]
+    fn test_prefix_match() {
+        let results = search_emojis("fir", 10);
+        assert!(!results.is_empty());
+    }
+
+    #[test]
+    fn test_substring_match() {
+        let results = search_emojis("face", 10);
+        assert!(!results.is_empty());
+    }
+
+    #[test]
+    fn test_flag_search() {
+        let results = search_emojis("aus", 10);
+        assert!(!results.is_empty());
+        // Should find Australia and Austria flags
+    }
+
+    #[test]
+    fn test_limit() {
+        let results = search_emojis("a", 5);
+        assert!(results.len() <= 5);
+    }
+
+    #[test]
+    fn test_combined_results_suffix() {
+        // Test with "fire+"
+        let query = vec!["fire+".to_string()];
+        let results = find_emojis(&query, 10, &None, &None);
+
+        assert_eq!(results.len(), 1);
+        let SearchResult { term, emoji, .. } = &results[0];
+        assert_eq!(term, "fire");
+        // Should contain multiple fire emojis concatenated
+        assert!(emoji.contains("🔥"));
+        assert!(emoji.chars().count() > 1);
+    }
+
+    #[test]
+    fn test_combined_results_separate_arg() {
+        // Test with "fire +"
+        let query = vec!["fire".to_string(), "+".to_string()];
+        let results = find_emojis(&query, 10, &None, &None);
+
+        assert_eq!(results.len(), 1);
+        let SearchResult { term, emoji, .. } = &results[0];
+        assert_eq!(term, "fire");
+        assert!(emoji.contains("🔥"));
+        assert!(emoji.chars().count() > 1);
+    }
+
+    #[test]
+    fn test_combined_results_no_plus() {
+        // Test with "fire" (normal behavior)
+        let query = vec!["fire".to_string()];
+        let results = find_emojis(&query, 10, &None, &None);
+
+        assert!(results.len() > 1); // Should return multiple individual results
+    }
+
+    #[test]
+    fn test_combined_results_empty_plus() {
+        // Test with "+"
+        let query = vec!["+".to_string()];
+        let results = find_emojis(&query, 10, &None, &None);
+
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_relaxed_substring_matches_slang() {
+        let results = search_emojis("factor", 10);
+        assert!(results.iter().any(|hit| hit.term == "refactor"));
+    }
+
+    #[test]
+    fn test_relaxed_substring_matches_standard_name() {
+        let results = search_emojis("factor", 10);
+        assert!(results.iter().any(|hit| hit
+            .emoji
+            .unicode()
+            .is_some_and(|emoji| emoji.name().to_lowercase() == "factory")));
+    }
+
+    #[test]
+    fn test_no_relaxed_substring_for_two_char_queries() {
+        // Should not match via relaxed substring when the query is only 2 chars
+        let results = search_emojis("ct", 10);
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_slang_combo_is_a_sequence() {
+        let results = search_emojis("cunt", 10);
+        let combo = results
+            .iter()
+            .find(|hit| hit.emoji == EmojiMatch::Sequence("💅👑"))
+            .expect("combo should be returned as a sequence");
+        assert_eq!(combo.term, "cunt");
+        assert_eq!(combo.stage, MatchStage::ExactSlang);
+        assert_eq!(combo.source, MatchSource::Slang);
+    }
+
+    #[test]
+    fn test_skin_tone_applies_to_combo_parts() {
+        let parts = split_sequence("💅👑");
+        assert!(matches!(parts.as_slice(), [Ok(_), Ok(_)]));
+
+        let searcher = Searcher::new(SearchOptions {
+            skin_tone: Some(SkinTone::Dark),
+            ..Default::default()
+        });
+        let results = searcher.search("cunt");
+        assert!(results.iter().any(|r| r.emoji == "💅🏿👑"));
+    }
+
+    #[test]
+    fn test_ranking_is_deterministic() {
+        let first = search_emojis("fir", 20);
+        for _ in 0..5 {
+            assert_eq!(search_emojis("fir", 20), first);
+        }
+    }
+
+    #[test]
+    fn test_results_sorted_by_stage_and_score() {
+        let results = search_emojis("heart", 50);
+        assert!(results
+            .windows(2)
+            .all(|pair| pair[0].stage <= pair[1].stage && pair[0].score >= pair[1].score));
+    }
+
+    #[test]
+    fn test_explanation_records_compared_strings() {
+        let results = search_emojis("pizza", 10);
+        let exact = &results[0].explanation;
+        assert_eq!(exact.field, "pizza");
+        assert_eq!(exact.comparison, Comparison::Equals);
+
+        // A one-letter shortcode matches because the query contains it
+        let contained = results
+            .iter()
+            .find(|m| m.term == "a")
+            .map(|m| &m.explanation)
+            .unwrap();
+        assert_eq!(contained.comparison, Comparison::RelaxedContainedIn);
+        assert_eq!(contained.query, "pizza");
+    }
+
+    #[test]
+    fn test_fuzzy_tolerates_typos() {
+        let results = search_emojis("firr", 10);
+        assert!(results
+            .iter()
+            .any(|m| m.emoji.base() == "🔥" && m.stage == MatchStage::Fuzzy));
+    }
+
+    #[test]
+    fn test_fuzzy_ranks_below_other_stages() {
+        let results = search_emojis("rainbwo", 50);
+        let first_fuzzy = results.iter().position(|m| m.stage == MatchStage::Fuzzy);
+        assert!(first_fuzzy.is_some());
+        assert!(results[first_fuzzy.unwrap()..]
+            .iter()
+            .all(|m| m.stage == MatchStage::Fuzzy));
+    }
+
+    #[test]
+    fn test_fuzzy_can_be_disabled() {
+        let searcher = Searcher::new(SearchOptions {
+            fuzzy: false,
+            ..Default::default()
+        });
+        assert!(searcher.search("firr").is_empty());
+    }
+
+    #[test]
+    fn test_multi_word_query_matches_every_word() {
+        let searcher = Searcher::default();
+        for query in ["face cold", "cold face"] {
+            let results = searcher.search(query);
+            assert_eq!(results[0].emoji, "🥶", "{}", query);
+        }
+        let results = searcher.search("face cold");
+        assert_eq!(results[0].stage, MatchStage::AllWords);
+        assert_eq!(searcher.search("cat happy")[0].emoji, "😺");
+    }
+
+    #[test]
+    fn test_multi_word_query_needs_every_word() {
+        let searcher = Searcher::new(SearchOptions {
+            limit: 50,
+            ..Default::default()
+        });
+        for query in ["cat happy", "face cold", "heart red", "thumbs u"] {
+            let results = searcher.search(query);
+            assert!(!results.is_empty(), "{}", query);
+            for result in results {
+                let field = result.explanation.field;
+                assert!(
+                    result.stage == MatchStage::AllWords
+                        || query.split(' ').all(|word| field.contains(word)),
+                    "{} matched only part of {:?} in {:?}",
+                    result.emoji,
+                    query,
+                    field
+                );
+            }
+        }
+        // Substring hits on the joined words, like "cat" or "a" in "cathappy", are gone
+        let cat_happy = emojis("cat happy");
+        for single in ["🐈", "🐱", "🐉", "🅰️", "😊"] {
+            assert!(!cat_happy.contains(&single.to_string()), "{}", single);
+        }
+    }
+
+    #[test]
+    fn test_multi_word_query_ignores_order() {
+        // Joined up the words can also be a typo of a whole name, so compare the word matches
+        let word_matches = |query| -> Vec<String> {
+            let mut emojis: Vec<String> = search_emojis(query, 50)
+                .into_iter()
+                .filter(|m| m.stage <= MatchStage::AllWords)
+                .map(|m| m.emoji.base())
+                .collect();
+            emojis.sort();
+            emojis
+        };
+        assert!(word_matches("red heart").contains(&"❤️".to_string()));
+        assert_eq!(word_matches("red heart"), word_matches("heart red"));
+    }
+
+    fn emojis(query: &str) -> Vec<String> {
+        Searcher::new(SearchOptions {
+            limit: 50,
+            ..Default::default()
+        })
+        .try_search(query)
+        .unwrap()
+        .into_iter()
+        .map(|r| r.emoji)
+        .collect()
+    }
+
+    #[test]
+    fn test_query_negation() {
+        let results = emojis("heart -broken");
+        assert!(results.contains(&"❤️".to_string()));
+        assert!(!results.contains(&"💔".to_string()));
+    }
+
+    #[test]
+    fn test_query_alternatives() {
+        let results = emojis("cat|dog");
+        assert!(results.contains(&"🐈".to_string()));
+        assert!(results.contains(&"🐕".to_string()));
+    }
+
+    #[test]
+    fn test_query_phrase() {
+        let results = emojis("\"smiling face\"");
+        assert!(results.contains(&"☺️".to_string()));
+        // Has both words, but not as a phrase
+        assert!(!results.contains(&"😄".to_string()));
+    }
+
+    #[test]
+    fn test_query_fields() {
+        let searcher = Searcher::default();
+        let shortcodes = searcher.try_search("sc:thumbsup").unwrap();
+        assert_eq!(shortcodes[0].emoji, "👍");
+        assert!(shortcodes
+            .iter()
+            .all(|r| r.source == MatchSource::Shortcode));
+
+        let slang = searcher.try_search("slang:cap").unwrap();
+        assert_eq!(slang[0].emoji, "🧢");
+        assert!(slang.iter().all(|r| r.source == MatchSource::Slang));
+    }
+
+    #[test]
+    fn test_malformed_query_is_an_error() {
+        let searcher = Searcher::default();
+        assert_eq!(
+            searcher.try_search("cat|"),
+            Err(QueryError::EmptyAlternative)
+        );
+        assert!(searcher.search("cat|").is_empty());
+    }
+
+    #[test]
+    fn test_facets_filter_results() {
+        let searcher = Searcher::new(SearchOptions {
+            facets: Facets {
+                subgroup: Some("animal-mammal"),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let results = searcher.search("dog");
+        assert!(!results.is_empty());
+        assert!(results.iter().all(|r| {
+            let emoji = r.matched.unicode().unwrap();
+            crate::subgroup_of(emoji) == Some("animal-mammal")
+        }));
+    }
+
+    #[test]
+    fn test_empty_query_browses_facets() {
+        let searcher = Searcher::new(SearchOptions {
+            limit: 3,
+            facets: Facets {
+                group: Some(emojis::Group::FoodAndDrink),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        let results = searcher.search("");
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].emoji, "🍇");
+        assert!(results.iter().all(|r| r.stage == MatchStage::Browse));
+        assert!(Searcher::default().search("").is_empty());
+    }
+
+    #[test]
+    fn test_cldr_name_colons_are_searched() {
+        let searcher = Searcher::default();
+        for query in ["flag: japan", "flag:japan"] {
+            let results = searcher.try_search(query).unwrap();
+            assert_eq!(results[0].emoji, "🇯🇵", "{}", query);
+        }
+        let results = searcher.try_search("keycap: #").unwrap();
+        assert_eq!(results[0].emoji, "#️⃣");
+    }
+
+    #[test]
+    fn test_minus_one_shortcode() {
+        let results = Searcher::default().try_search("-1").unwrap();
+        assert_eq!(results[0].emoji, "👎");
+    }
+}
@@ -0,0 +1,11 @@
+/// A slang term's emojis and the category it is listed under in `data/slang.toml`
+#[derive(Debug)]
+pub struct Slang {
+    /// Emojis for the term, best match first; an entry may be a multi-emoji combo
+    pub emojis: &'static [&'static str],
+    /// Category table the term is listed under (e.g. "gen-z")
+    pub category: &'static str,
+}
+
+// `SLANG_MAP`, compiled from data/slang.toml by build.rs
+include!(concat!(env!("OUT_DIR"), "/slang_map.rs"));
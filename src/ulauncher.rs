@@ -0,0 +1,116 @@
+use crate::model::SearchResult;
+use crate::output::match_name;
+use crate::query::QueryError;
+use serde::Serialize;
+
+/// A row for Ulauncher, with the arguments of `ExtensionResultItem`
+#[derive(Serialize, Debug)]
+pub struct UlauncherItem {
+    /// Item title, the emoji followed by the matched term
+    pub name: String,
+    pub description: String,
+    /// What Enter does, absent for placeholder rows
+    pub on_enter: Option<UlauncherAction>,
+}
+
+/// An action an extension maps to Ulauncher's action classes
+#[derive(Serialize, Debug)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum UlauncherAction {
+    /// `CopyToClipboardAction(text)`
+    Copy { text: String },
+}
+
+/// Top-level JSON document for a Ulauncher extension's results
+#[derive(Serialize, Debug)]
+pub struct UlauncherResponse {
+    pub items: Vec<UlauncherItem>,
+}
+
+impl UlauncherItem {
+    /// Build a Ulauncher item for a search result
+    pub fn from_result(result: SearchResult) -> Self {
+        let mut description = match_name(&result.matched);
+        if let Some(emoji) = result.matched.unicode() {
+            for shortcode in emoji.shortcodes() {
+                description.push_str(&format!(" :{}:", shortcode));
+            }
+        }
+
+        UlauncherItem {
+            name: format!("{} {}", result.emoji, result.term),
+            description,
+            on_enter: Some(UlauncherAction::Copy { text: result.emoji }),
+        }
+    }
+
+    /// A row that does nothing on Enter
+    fn placeholder(name: &str, description: String) -> Self {
+        UlauncherItem {
+            name: name.to_string(),
+            description,
+            on_enter: None,
+        }
+    }
+}
+
+impl UlauncherResponse {
+    /// Build a Ulauncher response listing the search results
+    pub fn from_results(results: Vec<SearchResult>) -> Self {
+        UlauncherResponse {
+            items: results
+                .into_iter()
+                .map(UlauncherItem::from_result)
+                .collect(),
+        }
+    }
+
+    /// Placeholder response shown when nothing matched
+    pub fn no_results() -> Self {
+        UlauncherResponse {
+            items: vec![UlauncherItem::placeholder(
+                "No emojis found",
+                "Try a different search term".to_string(),
+            )],
+        }
+    }
+
+    /// Create a response explaining why the query could not be parsed
+    pub fn invalid_query(err: &QueryError) -> Self {
+        UlauncherResponse {
+            items: vec![UlauncherItem::placeholder("Invalid query", err.to_string())],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::search::Searcher;
+
+    #[test]
+    fn test_result_items() {
+        let results = Searcher::default().search("fire");
+        let json = serde_json::to_value(UlauncherResponse::from_results(results)).unwrap();
+        assert_eq!(
+            json["items"][0],
+            serde_json::json!({
+                "name": "🔥 fire",
+                "description": "fire :fire:",
+                "on_enter": { "type": "copy", "text": "🔥" }
+            })
+        );
+    }
+
+    #[test]
+    fn test_placeholder_responses() {
+        let response = UlauncherResponse::no_results();
+        assert_eq!(response.items[0].name, "No emojis found");
+        assert!(response.items[0].on_enter.is_none());
+
+        let err = QueryError::EmptyPhrase;
+        let response = UlauncherResponse::invalid_query(&err);
+        assert_eq!(response.items[0].description, "empty quoted phrase");
+        assert!(response.items[0].on_enter.is_none());
+    }
+}
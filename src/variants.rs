@@ -0,0 +1,93 @@
+/// Gender variant requested for emojis that support it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Gender {
+    Male,
+    Female,
+    Neutral,
+}
+
+/// Skin tone variant requested for emojis that support it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum SkinTone {
+    Light,
+    MediumLight,
+    Medium,
+    MediumDark,
+    Dark,
+}
+
+/// Apply skin tone modifier to emoji if applicable
+pub fn apply_skin_tone(emoji: &emojis::Emoji, skin_tone: &SkinTone) -> String {
+    let base = emoji.as_str();
+
+    // Check if emoji has skin tone variants
+    if emoji.skin_tones().is_some() {
+        let modifier = match skin_tone {
+            SkinTone::Light => "\u{1F3FB}",       // 🏻
+            SkinTone::MediumLight => "\u{1F3FC}", // 🏼
+            SkinTone::Medium => "\u{1F3FD}",      // 🏽
+            SkinTone::MediumDark => "\u{1F3FE}",  // 🏾
+            SkinTone::Dark => "\u{1F3FF}",        // 🏿
+        };
+
+        // For compound emojis with ZWJ sequences, we need to insert the skin tone
+        // modifier after the first emoji character, not at the end
+        let chars: Vec<char> = base.chars().collect();
+        if chars.len() > 1 {
+            // Find the first emoji character and insert skin tone after it
+            let first_char = chars[0];
+            let rest: String = chars[1..].iter().collect();
+            return format!("{}{}{}", first_char, modifier, rest);
+        } else {
+            // Simple emoji, just append
+            return format!("{}{}", base, modifier);
+        }
+    }
+
+    base.to_string()
+}
+
+/// Apply gender modifier to emoji if applicable
+/// Note: Gender variants are already in the emoji database (e.g., "man firefighter", "woman firefighter")
+/// so we don't apply gender modifiers programmatically as they can render incorrectly
+pub fn apply_gender(emoji_str: &str, _gender: &Gender) -> String {
+    // Return emoji as-is since gendered variants are already in the database
+    emoji_str.to_string()
+}
+
+/// Split a string of emojis into database entries, longest match first
+///
+/// Each run of text that is not a known emoji is returned as one `Err` so callers can keep
+/// it verbatim.
+pub(crate) fn split_sequence(sequence: &str) -> Vec<Result<&'static emojis::Emoji, &str>> {
+    let mut parts: Vec<Result<&emojis::Emoji, &str>> = Vec::new();
+    let mut rest = sequence;
+
+    while let Some(first) = rest.chars().next() {
+        let found = rest
+            .char_indices()
+            .map(|(i, c)| i + c.len_utf8())
+            .rev()
+            .find_map(|end| emojis::get(&rest[..end]).map(|emoji| (emoji, end)));
+
+        match found {
+            Some((emoji, end)) => {
+                parts.push(Ok(emoji));
+                rest = &rest[end..];
+            }
+            None => {
+                let start = sequence.len() - rest.len();
+                let end = start + first.len_utf8();
+                match parts.last_mut() {
+                    Some(Err(text)) => *text = &sequence[start - text.len()..end],
+                    _ => parts.push(Err(&sequence[start..end])),
+                }
+                rest = &sequence[end..];
+            }
+        }
+    }
+
+    parts
+}